@@ -32,8 +32,47 @@ pub enum Error {
         r#enum: usize,
         discriminant: usize,
     },
-    VariantDiscriminant,
     VariantNonZero,
+    DiscriminantSize {
+        required: usize,
+        actual: usize,
+    },
+    DiscriminantOverflow {
+        offset: usize,
+        discriminant: usize,
+        r#enum: usize,
+    },
+    DiscriminantOverlap {
+        variant: usize,
+        offset: usize,
+    },
+    PrefixDiscriminant,
+    PrefixSize {
+        r#enum: usize,
+    },
+    NonExhaustiveDuplicate,
+    NonExhaustiveVariant {
+        r#enum: Tight,
+    },
+    NonExhaustiveFull,
+    AtomicEnum,
+    GuardType,
+    GuardPosition {
+        loose: usize,
+    },
+    NameCollision {
+        name: String,
+    },
+    OptionType,
+    ArrayLength,
+    ArraySize {
+        size: usize,
+        len: usize,
+    },
+    /// Feature that is only supported for packed types up to 128 bits.
+    Wide {
+        feature: &'static str,
+    },
 }
 
 macro_rules! bail {
@@ -79,7 +118,7 @@ impl Display for Error {
                 )
             }
             Error::ArbitrarySize { size } => {
-                write!(f, "Arbitrary size {size} unsupported")
+                write!(f, "Arbitrary size {size} unsupported (maximum is 512)")
             }
             Error::UnsupportedType => {
                 write!(f, "Only type paths are supported")
@@ -97,18 +136,94 @@ impl Display for Error {
                     "Variant of size {variant} does not fit in enum of size {enum} with discriminant of size {discriminant}",
                 )
             }
-            Error::VariantDiscriminant => {
+            Error::VariantNonZero => {
                 write!(
                     f,
-                    "Only literal integers are currently supported for variant discriminant",
+                    "Non-zero enum requires either all non-zero discriminants *or* variant with discriminant 0 to be non-zero",
                 )
             }
-            Error::VariantNonZero => {
+            Error::DiscriminantSize { required, actual } => {
                 write!(
                     f,
-                    "Non-zero enum requires either all non-zero discriminants *or* variant with discriminant 0 to be non-zero",
+                    "Discriminant of size {actual} is too small, discriminant values require {required} bits",
+                )
+            }
+            Error::DiscriminantOverflow {
+                offset,
+                discriminant,
+                r#enum,
+            } => {
+                write!(
+                    f,
+                    "Discriminant of size {discriminant} at offset {offset} does not fit in enum of size {enum}",
+                )
+            }
+            Error::DiscriminantOverlap { variant, offset } => {
+                write!(
+                    f,
+                    "Variant of size {variant} overlaps discriminant at offset {offset}",
                 )
             }
+            Error::PrefixDiscriminant => {
+                write!(
+                    f,
+                    "Explicit discriminants are not supported with #[ribbit(discriminant = \"prefix\")]",
+                )
+            }
+            Error::PrefixSize { r#enum } => {
+                write!(
+                    f,
+                    "Variants do not fit in enum of size {enum} with prefix discriminants",
+                )
+            }
+            Error::NonExhaustiveDuplicate => {
+                write!(f, "At most one variant can be non-exhaustive")
+            }
+            Error::NonExhaustiveVariant { r#enum } => {
+                write!(
+                    f,
+                    "Non-exhaustive variant must have a single field of the same size as the enum: {enum}",
+                )
+            }
+            Error::NonExhaustiveFull => {
+                write!(
+                    f,
+                    "Non-exhaustive variant requires a discriminant not used by any other variant",
+                )
+            }
+            Error::AtomicEnum => {
+                write!(f, "derive(Atomic) is only supported for structs")
+            }
+            Error::GuardType => {
+                write!(f, "Guard field must be a `bool` or an integer type")
+            }
+            Error::GuardPosition { loose } => {
+                write!(
+                    f,
+                    "Guard field must be the most significant field, and end at bit {loose} of the loose type",
+                )
+            }
+            Error::NameCollision { name } => {
+                write!(
+                    f,
+                    "Generated name `{name}` is already in use, and must be renamed or skipped",
+                )
+            }
+            Error::OptionType => {
+                write!(
+                    f,
+                    "#[ribbit(option = ...)] requires a field of type Option<T>"
+                )
+            }
+            Error::ArrayLength => {
+                write!(f, "Array length must be a non-zero integer literal")
+            }
+            Error::ArraySize { size, len } => {
+                write!(f, "Array size {size} is not a multiple of its length {len}")
+            }
+            Error::Wide { feature } => {
+                write!(f, "{feature} is only supported up to 128 bits")
+            }
         }
     }
 }
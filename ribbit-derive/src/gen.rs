@@ -2,6 +2,15 @@
 pub(crate) mod packed;
 pub(crate) use packed::packed;
 
+/// Generate an associated constant on the unpacked type that evaluates discriminant
+/// expressions outside of the generated module, so that paths resolve in the caller's scope.
+pub(crate) mod discriminant;
+pub(crate) use discriminant::discriminant;
+
+/// Generate payload views, predicates, and accessors for enum variants.
+pub(crate) mod variant;
+pub(crate) use variant::variant;
+
 // Methods
 pub(crate) mod get;
 pub(crate) use get::get;
@@ -21,8 +30,23 @@ pub(crate) use into_raw::into_raw;
 pub(crate) mod from_raw_unchecked;
 pub(crate) use from_raw_unchecked::from_raw_unchecked;
 
+/// Generate a checked constructor from raw bits and a [`TryFrom`] implementation
+/// from the loose type, along with the validity rules they evaluate.
+pub(crate) mod try_from_raw;
+pub(crate) use try_from_raw::try_from;
+pub(crate) use try_from_raw::try_from_raw;
+
+/// Generate conversions to and from the minimum number of bytes that fit
+/// the packed type, in little-endian, big-endian, or native order.
+pub(crate) mod bytes;
+pub(crate) use bytes::bytes;
+
 // Traits
 
+/// Generate an extension trait for `ribbit::Atomic` with per-field atomic operations.
+pub(crate) mod atomic;
+pub(crate) use atomic::atomic;
+
 /// Generate a [`core::fmt::Debug`] implementation by forwarding to the unpacked type.
 pub(crate) mod debug;
 pub(crate) use debug::debug;
@@ -46,8 +70,13 @@ pub(crate) use hash::hash;
 pub(crate) mod ord;
 pub(crate) use ord::ord;
 
+/// Generate a [`ribbit::HasNiche`] implementation for enums with
+/// a spare discriminant, so that `ribbit::Niche` can represent `None`.
+pub(crate) mod niche;
+pub(crate) use niche::niche;
+
 mod pack;
 pub(crate) use pack::pack;
 
-mod unpack;
+pub(crate) mod unpack;
 pub(crate) use unpack::unpack;
@@ -0,0 +1,248 @@
+use darling::FromMeta;
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use syn::parse_quote;
+
+use crate::gen;
+use crate::ir;
+use crate::lift;
+
+#[derive(FromMeta, Clone, Debug)]
+pub(crate) struct ItemOpt;
+
+pub(crate) fn name(item: &ir::Item) -> syn::Ident {
+    format_ident!("{}AtomicExt", item.ident_unpacked())
+}
+
+pub(crate) fn atomic(item: &ir::Item) -> TokenStream {
+    if item.opt().derive.atomic.is_none() {
+        return TokenStream::new();
+    }
+
+    let ir::Data::Struct(r#struct) = &item.data else {
+        unreachable!("[INTERNAL ERROR]: derive(Atomic) on enum is rejected by ir::Item::new")
+    };
+
+    let methods = r#struct
+        .iter()
+        .filter(|field| !field.r#type.is_zst())
+        .flat_map(|field| atomic_field(item, r#struct, field))
+        .collect::<Vec<_>>();
+
+    let declarations = methods.iter().map(|(declaration, _)| declaration);
+    let definitions = methods.iter().map(|(_, definition)| definition);
+
+    let vis = item.opt().packed.vis_raised(&item.vis);
+    let r#trait = name(item);
+    let unpacked = item.ident_unpacked();
+    let loose = item.tight().to_loose();
+
+    let generics = item.generics_bounded();
+    let (generics_trait, generics_type, generics_where) = generics.split_for_impl();
+
+    let mut generics_raw = generics.clone();
+    generics_raw.params.push(parse_quote!(__R));
+    generics_raw
+        .make_where_clause()
+        .predicates
+        .push(parse_quote!(__R: ::ribbit::atomic::Raw<#loose>));
+    let (generics_impl, _, generics_impl_where) = generics_raw.split_for_impl();
+
+    quote! {
+        #vis trait #r#trait #generics_trait #generics_where {
+            #(#declarations)*
+        }
+
+        impl #generics_impl #r#trait #generics_type
+            for ::ribbit::Atomic<#unpacked #generics_type, __R>
+            #generics_impl_where
+        {
+            #(#definitions)*
+        }
+    }
+}
+
+fn atomic_field(
+    item: &ir::Item,
+    r#struct: &ir::Struct,
+    field: &ir::Field,
+) -> Vec<(TokenStream, TokenStream)> {
+    let get = (!field.opt.get.0.skip).then(|| gen::get::FieldOpt::name_whole(field));
+    let with = (!field.opt.with.0.skip).then(|| gen::with::FieldOpt::name_whole(field));
+    let r#type = field.r#type.packed();
+
+    let ordering = quote!(::core::sync::atomic::Ordering);
+    let mut methods = Vec::new();
+
+    if let Some(get) = &get {
+        let load = field.ident.prefix("load");
+        methods.push((
+            quote!(fn #load(&self, ordering: #ordering) -> #r#type;),
+            quote! {
+                #[inline]
+                fn #load(&self, ordering: #ordering) -> #r#type {
+                    self.load_packed(ordering).#get()
+                }
+            },
+        ));
+    }
+
+    if let Some(with) = &with {
+        let store = field.ident.prefix("store");
+        methods.push((
+            quote!(fn #store(&self, value: #r#type, ordering: #ordering);),
+            quote! {
+                #[inline]
+                fn #store(&self, value: #r#type, ordering: #ordering) {
+                    self.update_packed(
+                        ordering,
+                        ::ribbit::private::failure_ordering(ordering),
+                        |old| old.#with(value),
+                    );
+                }
+            },
+        ));
+    }
+
+    if let (Some(get), Some(with)) = (&get, &with) {
+        let swap = field.ident.prefix("swap");
+        let fetch_update = field.ident.prefix("fetch_update");
+        methods.push((
+            quote!(fn #swap(&self, value: #r#type, ordering: #ordering) -> #r#type;),
+            quote! {
+                #[inline]
+                fn #swap(&self, value: #r#type, ordering: #ordering) -> #r#type {
+                    self.update_packed(
+                        ordering,
+                        ::ribbit::private::failure_ordering(ordering),
+                        |old| old.#with(value),
+                    )
+                    .#get()
+                }
+            },
+        ));
+        methods.push((
+            quote! {
+                fn #fetch_update<F>(
+                    &self,
+                    set_order: #ordering,
+                    fetch_order: #ordering,
+                    f: F,
+                ) -> Result<#r#type, #r#type>
+                where
+                    F: FnMut(#r#type) -> Option<#r#type>;
+            },
+            quote! {
+                #[inline]
+                fn #fetch_update<F>(
+                    &self,
+                    set_order: #ordering,
+                    fetch_order: #ordering,
+                    mut f: F,
+                ) -> Result<#r#type, #r#type>
+                where
+                    F: FnMut(#r#type) -> Option<#r#type>,
+                {
+                    self.fetch_update_packed(set_order, fetch_order, |old| {
+                        f(old.#get()).map(|value| old.#with(value))
+                    })
+                    .map(|old| old.#get())
+                    .map_err(|old| old.#get())
+                }
+            },
+        ));
+    }
+
+    if let (Some(get), Some(with), true) = (&get, &with, field.r#type.is_integer()) {
+        let loose = item.tight().to_loose();
+
+        let native = native(r#struct, field, loose.size());
+        for (prefix, operation, wrapping) in [
+            ("fetch_add", quote!(fetch_add_loose), quote!(wrapping_add)),
+            ("fetch_sub", quote!(fetch_sub_loose), quote!(wrapping_sub)),
+        ] {
+            let name = field.ident.prefix(prefix);
+            let body = match native {
+                true => {
+                    let value = lift::Expr::value(quote!(value), &field.r#type)
+                        .shift_left(field.offset as u8)
+                        .compile(loose);
+                    quote! {
+                        // SAFETY: carry out of the field either falls off the end
+                        // of the loose type, or lands in a guard field that is
+                        // valid for any bit pattern
+                        unsafe { self.#operation(#value, ordering) }.#get()
+                    }
+                }
+                false => quote! {
+                    self.update_packed(
+                        ordering,
+                        ::ribbit::private::failure_ordering(ordering),
+                        |old| old.#with(old.#get().#wrapping(value)),
+                    )
+                    .#get()
+                },
+            };
+
+            methods.push((
+                quote!(fn #name(&self, value: #r#type, ordering: #ordering) -> #r#type;),
+                quote! {
+                    #[inline]
+                    fn #name(&self, value: #r#type, ordering: #ordering) -> #r#type {
+                        #body
+                    }
+                },
+            ));
+        }
+    }
+
+    // Single-bit fields can be updated with native read-modify-write operations
+    if field.r#type.size() == 1 && !field.r#type.is_user() && with.is_some() {
+        let packed = item.ident_packed();
+        let (_, generics_type, _) = item.generics().split_for_impl();
+        let loose = item.tight().to_loose();
+        let bit = 1u128 << field.offset;
+
+        let operations = [
+            ("set", quote!(fetch_or_loose), bit),
+            (
+                "clear",
+                quote!(fetch_and_loose),
+                !bit & crate::mask(loose.size()),
+            ),
+            ("toggle", quote!(fetch_xor_loose), bit),
+        ];
+
+        for (prefix, operation, value) in operations {
+            let name = field.ident.prefix(prefix);
+            let value = loose.literal(value);
+            methods.push((
+                quote!(fn #name(&self, ordering: #ordering) -> #packed #generics_type;),
+                quote! {
+                    #[inline]
+                    fn #name(&self, ordering: #ordering) -> #packed #generics_type {
+                        // SAFETY: any value is valid for a single-bit field
+                        unsafe { self.#operation(#value, ordering) }
+                    }
+                },
+            ));
+        }
+    }
+
+    methods
+}
+
+/// Whether carry out of `field` cannot affect other fields, so that native
+/// atomic arithmetic on the loose type is safe.
+///
+/// This holds when the field ends at the top of the loose type, so carry falls off
+/// the end, or when the field directly above it is a guard field, which
+/// `ir::Struct::new` checks is the most significant field and ends at the top.
+fn native(r#struct: &ir::Struct, field: &ir::Field, loose: usize) -> bool {
+    let end = field.offset + field.r#type.size();
+    end == loose
+        || r#struct.iter().any(|above| {
+            *above.opt.guard && above.offset == r#struct.max_offset && above.offset == end
+        })
+}
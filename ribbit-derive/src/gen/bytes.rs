@@ -0,0 +1,73 @@
+use std::borrow::Cow;
+
+use darling::FromMeta;
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+
+use crate::ir;
+
+#[derive(FromMeta, Clone, Debug, Default)]
+pub(crate) struct ItemOpt(pub(crate) ir::CommonOpt);
+
+const ORDERS: [&str; 3] = ["le", "be", "ne"];
+
+impl ItemOpt {
+    /// Names of the conversions to and from bytes in each byte order, where
+    /// `rename` replaces the `bytes` suffix (e.g., `to_le_bytes`).
+    pub(crate) fn names<'ir>(
+        &'ir self,
+        item: &'ir ir::Item,
+    ) -> impl Iterator<Item = (&'static str, syn::Ident, Option<syn::Ident>)> + 'ir {
+        let suffix = self.0.rename_with(|| Cow::Owned(format_ident!("bytes")));
+        let checked = !item.opt().try_from_raw.0.skip;
+
+        ORDERS.into_iter().map(move |order| {
+            (
+                order,
+                format_ident!("to_{}_{}", order, suffix),
+                checked.then(|| format_ident!("try_from_{}_{}", order, suffix)),
+            )
+        })
+    }
+}
+
+pub(crate) fn bytes(item: &ir::Item) -> TokenStream {
+    let opt = &item.opt().bytes;
+
+    if opt.0.skip {
+        return TokenStream::default();
+    }
+
+    let vis = opt.0.vis(&item.vis);
+    let size = item.tight().size().div_ceil(8);
+    let precondition = crate::gen::precondition::assert();
+
+    let methods = opt.names(item).map(|(order, to, try_from)| {
+        let to_private = format_ident!("to_{}_bytes", order);
+        let try_from_private = format_ident!("try_from_{}_bytes", order);
+
+        // Validates with the same rules as `try_from_raw`
+        let try_from = try_from.map(|try_from| {
+            quote! {
+                #[inline]
+                #vis const fn #try_from(bytes: [u8; #size]) -> Result<Self, ::ribbit::InvalidBits> {
+                    #precondition
+                    ::ribbit::private::#try_from_private(bytes)
+                }
+            }
+        });
+
+        quote! {
+            #[inline]
+            #vis const fn #to(self) -> [u8; #size] {
+                #precondition
+                ::ribbit::private::#to_private(self)
+            }
+
+            #try_from
+        }
+    });
+
+    quote!(#(#methods)*)
+}
@@ -0,0 +1,30 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::ir;
+
+pub(crate) fn discriminant(item: &ir::Item) -> TokenStream {
+    let ir::Data::Enum(r#enum) = &item.data else {
+        return TokenStream::new();
+    };
+
+    if r#enum.expressions.is_empty() {
+        return TokenStream::new();
+    }
+
+    let unpacked = item.ident_unpacked();
+    let count = r#enum.expressions.len();
+    let expressions = r#enum.expressions.iter();
+
+    let generics = item.generics_bounded();
+    let (generics_impl, generics_type, generics_where) = generics.split_for_impl();
+
+    // Associated to the unpacked type, so it can't collide with the caller's
+    // items or with the constants of other enums in the same module
+    quote! {
+        impl #generics_impl #unpacked #generics_type #generics_where {
+            #[doc(hidden)]
+            const _RIBBIT_DISCRIMINANTS: [i128; #count] = [#((#expressions) as i128),*];
+        }
+    }
+}
@@ -13,7 +13,7 @@ use crate::r#type::Tight;
 use crate::Or;
 
 #[derive(FromMeta, Clone, Debug, Default)]
-pub(crate) struct VariantOpt(ir::CommonOpt);
+pub(crate) struct VariantOpt(pub(crate) ir::CommonOpt);
 
 pub(crate) fn from_raw_unchecked<'ir>(
     item: &'ir ir::Item,
@@ -43,8 +43,10 @@ pub(crate) fn from_raw_unchecked<'ir>(
 
                 let compile = |expr: lift::Expr| {
                     lift::Expr::or([
-                        lift::Expr::constant(variant.discriminant as u128),
-                        expr.shift_left(r#enum.discriminant.size as u8),
+                        r#enum
+                            .discriminant(variant)
+                            .shift_left(r#enum.discriminant.offset as u8),
+                        expr.shift_left(r#enum.offset_payload(variant) as u8),
                     ])
                     .compile(tight)
                 };
@@ -68,21 +70,24 @@ fn from_raw_unchecked_struct<'ir, F: FnOnce(lift::Expr<'ir>) -> TokenStream>(
 ) -> TokenStream {
     let precondition = crate::gen::precondition::assert();
     let raw = compile(lift::Expr::value_tight(quote!(raw), tight));
+    let check = crate::gen::unpack::check(quote!(packed));
 
     quote! {
         #[inline]
         #vis const unsafe fn #name(raw: #tight) -> Self {
             #precondition
-            Self {
+            let packed = Self {
                 value: #raw,
                 r#type: ::ribbit::PhantomData,
-            }
+            };
+            #check
+            packed
         }
     }
 }
 
 impl VariantOpt {
-    fn name<'ir>(&'ir self, variant: Option<&syn::Ident>) -> Cow<'ir, syn::Ident> {
+    pub(crate) fn name<'ir>(&'ir self, variant: Option<&syn::Ident>) -> Cow<'ir, syn::Ident> {
         self.0.rename_with(|| match variant {
             None => Cow::Owned(format_ident!("from_raw_unchecked")),
             Some(variant) => Cow::Owned(format_ident!(
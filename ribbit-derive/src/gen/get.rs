@@ -1,62 +1,170 @@
+use core::iter;
 use std::borrow::Cow;
 
 use darling::FromMeta;
+use heck::ToSnakeCase as _;
 use proc_macro2::TokenStream;
+use quote::format_ident;
 use quote::quote;
 
 use crate::ir;
 use crate::lift;
+use crate::r#type::Loose;
 use crate::r#type::Tight;
 use crate::Or;
+use crate::Type;
 
 #[derive(FromMeta, Clone, Debug, Default)]
-pub(crate) struct FieldOpt(ir::CommonOpt);
+pub(crate) struct FieldOpt(pub(crate) ir::CommonOpt);
 
 impl FieldOpt {
     pub(crate) fn name<'ir>(field: &'ir ir::Field) -> Cow<'ir, syn::Ident> {
         field.opt.get.0.rename_with(|| field.ident.escape())
     }
+
+    /// Name of the getter for the whole value of `field`, which is suffixed
+    /// with `_array` for arrays to make room for the indexed getter.
+    pub(crate) fn name_whole<'ir>(field: &'ir ir::Field) -> Cow<'ir, syn::Ident> {
+        let name = Self::name(field);
+        match field.r#type.as_array() {
+            None => name,
+            Some(_) => Cow::Owned(format_ident!("{}_array", name)),
+        }
+    }
+
+    /// Name of the getter for `field` of enum `variant`.
+    pub(crate) fn name_variant<'ir>(
+        variant: &ir::Variant,
+        field: &'ir ir::Field,
+    ) -> Cow<'ir, syn::Ident> {
+        field.opt.get.0.rename_with(|| {
+            Cow::Owned(
+                field
+                    .ident
+                    .prefix(&variant.ident.to_string().to_snake_case()),
+            )
+        })
+    }
 }
 
 pub(crate) fn get<'ir>(item: &'ir ir::Item) -> impl Iterator<Item = TokenStream> + 'ir {
-    let ir::Data::Struct(r#struct) = &item.data else {
-        return Or::L(core::iter::empty());
-    };
+    match &item.data {
+        ir::Data::Struct(r#struct) => {
+            Or::L(get_struct(r#struct, crate::gen::precondition::assert()))
+        }
+        ir::Data::Enum(r#enum) => Or::R(get_enum(r#enum)),
+    }
+}
 
-    let precondition = crate::gen::precondition::assert();
+fn get_enum<'ir>(r#enum: &'ir ir::Enum) -> impl Iterator<Item = TokenStream> + 'ir {
+    r#enum.variants.iter().flat_map(move |variant| {
+        let matches = crate::gen::variant::matches(r#enum, variant);
 
-    Or::R({
-        r#struct
+        variant
+            .r#struct
             .iter()
             .filter(|field| !field.r#type.is_zst())
             .filter(|field| !field.opt.get.0.skip)
             .map(move |field| {
-                let value = get_field(
-                    &r#struct.tight,
-                    field,
-                    r#struct.max_offset,
-                    field.offset as u8,
-                );
+                let value = get_variant_field(r#enum, variant, field);
                 let vis = field.opt.get.0.vis(&field.vis);
-                let name = FieldOpt::name(field);
+                let name = FieldOpt::name_variant(variant, field);
                 let r#type = field.r#type.packed();
+                let precondition = crate::gen::precondition::assert();
 
                 quote! {
                     #[inline]
-                    #vis const fn #name(self) -> #r#type {
+                    #vis const fn #name(self) -> Option<#r#type> {
                         #precondition
-                        #value
+                        match #matches {
+                            true => Some(#value),
+                            false => None,
+                        }
                     }
                 }
             })
     })
 }
 
+pub(crate) fn get_struct<'ir>(
+    r#struct: &'ir ir::Struct,
+    precondition: TokenStream,
+) -> impl Iterator<Item = TokenStream> + 'ir {
+    r#struct
+        .iter()
+        .filter(|field| !field.r#type.is_zst())
+        .filter(|field| !field.opt.get.0.skip)
+        .flat_map(move |field| {
+            let value = get_field(&r#struct.tight, field, r#struct.max_offset, field.offset);
+            let vis = field.opt.get.0.vis(&field.vis);
+            let name = FieldOpt::name_whole(field);
+            let r#type = field.r#type.packed();
+
+            let element = field.r#type.as_array().map(|(inner, len)| {
+                let value = get_element(&r#struct.tight, field.offset, inner, len);
+                let name = FieldOpt::name(field);
+                let r#type = inner.packed();
+
+                quote! {
+                    #[inline]
+                    #vis const fn #name(self, index: usize) -> #r#type {
+                        #precondition
+                        #value
+                    }
+                }
+            });
+
+            iter::once(quote! {
+                #[inline]
+                #vis const fn #name(self) -> #r#type {
+                    #precondition
+                    #value
+                }
+            })
+            .chain(element)
+        })
+}
+
+/// Get element `index` of an array at `offset`, panicking if `index` is out of bounds.
+fn get_element(tight: &Tight, offset: usize, inner: &Type, len: usize) -> TokenStream {
+    let stride = inner.size();
+    let bits = match tight.is_words() {
+        true => quote!(self.value.bits(#offset + index * #stride, #stride)),
+        false => {
+            let loose = tight.convert_to_loose(quote!(self.value));
+            quote!((#loose >> (#offset + index * #stride)))
+        }
+    };
+
+    let value = inner.convert_from_bits(bits);
+    quote! {
+        assert!(index < #len, "array index out of bounds");
+        #value
+    }
+}
+
+/// Get `field` of enum `variant`, assuming `self` holds `variant`.
+pub(crate) fn get_variant_field(
+    r#enum: &ir::Enum,
+    variant: &ir::Variant,
+    field: &ir::Field,
+) -> TokenStream {
+    let offset = r#enum.offset_payload(variant);
+
+    // Discriminant above the payload must be masked off
+    let max_offset = match r#enum.discriminant.offset {
+        0 => offset + variant.r#struct.max_offset,
+        _ => usize::MAX,
+    };
+
+    get_field(&r#enum.tight, field, max_offset, offset + field.offset)
+}
+
 pub(crate) fn get_field(
     tight: &Tight,
     field: &ir::Field,
     max_offset: usize,
-    offset: u8,
+    offset: usize,
 ) -> TokenStream {
     // ZSTs can be at `offset == r#type.size()`, which causes
     // the right-shift to error. Hack around this by setting
@@ -66,15 +174,36 @@ pub(crate) fn get_field(
         _ => offset,
     };
 
-    let expr = lift::Expr::value_self(tight).shift_right(offset);
+    if tight.is_words() {
+        return get_field_words(field, offset);
+    }
+
+    let expr = lift::Expr::value_self(tight).shift_right(offset as u8);
 
     // Loose type will be implicitly truncated by `as` cast
     match field.r#type.is_loose()
         // No other fields to mask
-        || offset as usize == max_offset
+        || offset == max_offset
     {
         true => expr,
         false => expr.and(field.r#type.mask()),
     }
     .compile(&*field.r#type)
 }
+
+/// Get `field` of a type wider than 128 bits, which can straddle word boundaries.
+fn get_field_words(field: &ir::Field, offset: usize) -> TokenStream {
+    let size = field.r#type.size();
+    let loose = field.r#type.to_loose();
+
+    if let Loose::Words(words) = loose {
+        let words = proc_macro2::Literal::usize_unsuffixed(words);
+        return field
+            .r#type
+            .convert_from_loose(quote!(self.value.words::<#words>(#offset, #size)));
+    }
+
+    let tight = loose.to_tight();
+    lift::Expr::value_tight(quote!((self.value.bits(#offset, #size) as #loose)), &tight)
+        .compile(&*field.r#type)
+}
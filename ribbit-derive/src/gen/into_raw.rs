@@ -8,7 +8,13 @@ use quote::quote;
 use crate::ir;
 
 #[derive(FromMeta, Clone, Debug, Default)]
-pub(crate) struct ItemOpt(ir::CommonOpt);
+pub(crate) struct ItemOpt(pub(crate) ir::CommonOpt);
+
+impl ItemOpt {
+    pub(crate) fn name(&self) -> Cow<'_, syn::Ident> {
+        self.0.rename_with(|| Cow::Owned(format_ident!("into_raw")))
+    }
+}
 
 pub(crate) fn into_raw(item: &ir::Item) -> TokenStream {
     let opt = &item.opt().into_raw;
@@ -18,7 +24,7 @@ pub(crate) fn into_raw(item: &ir::Item) -> TokenStream {
     }
 
     let vis = opt.0.vis(&item.vis);
-    let name = opt.0.rename_with(|| Cow::Owned(format_ident!("into_raw")));
+    let name = opt.name();
     let tight = item.tight();
     let precondition = crate::gen::precondition::assert();
 
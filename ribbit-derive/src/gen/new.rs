@@ -9,10 +9,11 @@ use quote::quote;
 
 use crate::ir;
 use crate::lift;
+use crate::r#type::Tight;
 use crate::Or;
 
 #[derive(FromMeta, Clone, Debug, Default)]
-pub(crate) struct VariantOpt(ir::CommonOpt);
+pub(crate) struct VariantOpt(pub(crate) ir::CommonOpt);
 
 pub(crate) fn new<'ir>(item: &'ir ir::Item) -> impl Iterator<Item = TokenStream> + 'ir {
     let opt = &item.opt().new;
@@ -29,6 +30,7 @@ pub(crate) fn new<'ir>(item: &'ir ir::Item) -> impl Iterator<Item = TokenStream>
             &opt.name(None),
             r#struct,
             |expr| expr.compile(tight),
+            None,
         )))),
         ir::Data::Enum(r#enum @ ir::Enum { variants, .. }) => {
             Or::R(Or::R(variants.iter().filter_map(move |variant| {
@@ -39,8 +41,10 @@ pub(crate) fn new<'ir>(item: &'ir ir::Item) -> impl Iterator<Item = TokenStream>
 
                 let compile = |expr: lift::Expr| {
                     lift::Expr::or([
-                        lift::Expr::constant(variant.discriminant as u128),
-                        expr.shift_left(r#enum.discriminant.size as u8),
+                        r#enum
+                            .discriminant(variant)
+                            .shift_left(r#enum.discriminant.offset as u8),
+                        expr.shift_left(r#enum.offset_payload(variant) as u8),
                     ])
                     .compile(tight)
                 };
@@ -50,17 +54,45 @@ pub(crate) fn new<'ir>(item: &'ir ir::Item) -> impl Iterator<Item = TokenStream>
                     &opt.name(Some(variant.ident)),
                     &variant.r#struct,
                     compile,
+                    variant
+                        .is_unknown()
+                        .then(|| validate_unknown(item, r#enum, variant)),
                 ))
             })))
         }
     }
 }
 
+/// Reject raw values of the catch-all variant that have a known discriminant,
+/// which would otherwise unpack as a different variant.
+fn validate_unknown(item: &ir::Item, r#enum: &ir::Enum, unknown: &ir::Variant) -> TokenStream {
+    let discriminant = crate::gen::unpack::discriminant(r#enum, quote!(packed));
+    let message = format!(
+        "Raw value of `{}::{}` has the discriminant of a known variant",
+        item.ident_unpacked(),
+        unknown.ident,
+    );
+
+    let known = r#enum
+        .variants
+        .iter()
+        .filter(|variant| !variant.is_unknown())
+        .map(|variant| crate::gen::unpack::pattern(r#enum, variant));
+
+    quote! {
+        match #discriminant {
+            #(#known => panic!(#message),)*
+            _ => (),
+        }
+    }
+}
+
 fn new_struct<'ir, F: FnOnce(lift::Expr<'ir>) -> TokenStream>(
     vis: &syn::Visibility,
     new: &syn::Ident,
     r#struct: &'ir ir::Struct,
     compile: F,
+    validate: Option<TokenStream>,
 ) -> TokenStream {
     let fields = r#struct.iter().filter(|field| !field.r#type.is_zst());
 
@@ -70,9 +102,15 @@ fn new_struct<'ir, F: FnOnce(lift::Expr<'ir>) -> TokenStream>(
         quote!(#ident: #r#type)
     });
 
-    let value = compile(lift::Expr::or(fields.clone().map(|field| {
-        lift::Expr::value(field.ident.escape(), &field.r#type).shift_left(field.offset as u8)
-    })));
+    let value = match &r#struct.tight {
+        tight @ Tight::Words(_) => fields.clone().fold(
+            quote!(<#tight as ::ribbit::Loose>::ZERO),
+            crate::gen::with::with_field_words,
+        ),
+        _ => compile(lift::Expr::or(fields.clone().map(|field| {
+            lift::Expr::value(field.ident.escape(), &field.r#type).shift_left(field.offset as u8)
+        }))),
+    };
 
     let precondition = crate::gen::precondition::assert();
 
@@ -82,10 +120,12 @@ fn new_struct<'ir, F: FnOnce(lift::Expr<'ir>) -> TokenStream>(
             #(#parameters),*
         ) -> Self {
             #precondition
-            Self {
+            let packed = Self {
                 value: #value,
                 r#type: ::ribbit::PhantomData,
-            }
+            };
+            #validate
+            packed
         }
     }
 }
@@ -0,0 +1,26 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::ir;
+
+pub(crate) fn niche(item: &ir::Item) -> TokenStream {
+    let ir::Data::Enum(r#enum) = &item.data else {
+        return TokenStream::new();
+    };
+
+    let Some(niche) = r#enum.niche else {
+        return TokenStream::new();
+    };
+
+    let generics = item.generics_bounded();
+    let (generics_impl, generics_type, generics_where) = generics.split_for_impl();
+    let packed = item.ident_packed();
+    let loose = r#enum.tight.to_loose();
+    let niche = proc_macro2::Literal::u128_unsuffixed(niche);
+
+    quote! {
+        unsafe impl #generics_impl ::ribbit::HasNiche for #packed #generics_type #generics_where {
+            const NICHE: #loose = #niche;
+        }
+    }
+}
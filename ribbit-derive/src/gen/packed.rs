@@ -25,16 +25,19 @@ impl ItemOpt {
     pub(crate) fn vis<'ir>(&'ir self, default: &'ir syn::Visibility) -> &'ir syn::Visibility {
         self.vis.as_ref().unwrap_or(default)
     }
+
+    /// Visibility of the packed type from inside the generated module.
+    pub(crate) fn vis_raised(&self, default: &syn::Visibility) -> syn::Visibility {
+        self.vis
+            .clone()
+            .map(ir::raise_vis)
+            .unwrap_or_else(|| default.clone())
+    }
 }
 
 pub(crate) fn packed(item: &ir::Item) -> TokenStream {
-    let opt = &item.opt().packed;
     let forward = &item.opt().forward;
-    let vis = opt
-        .vis
-        .clone()
-        .map(ir::raise_vis)
-        .unwrap_or_else(|| item.vis.clone());
+    let vis = item.opt().packed.vis_raised(&item.vis);
     let packed = item.ident_packed();
     let tight = item.tight();
 
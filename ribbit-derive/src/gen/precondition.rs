@@ -1,5 +1,3 @@
-use core::ops::Deref as _;
-
 use proc_macro2::TokenStream;
 use quote::quote;
 use quote::quote_spanned;
@@ -13,6 +11,13 @@ pub(crate) fn assert() -> TokenStream {
     }
 }
 
+/// Assert the precondition of the packed type from another type's methods.
+pub(crate) fn assert_of(packed: TokenStream) -> TokenStream {
+    quote! {
+        let _: () = <#packed>::_RIBBIT_PRECONDITION;
+    }
+}
+
 pub(crate) fn precondition(item: &ir::Item) -> TokenStream {
     let assertions = match &item.data {
         ir::Data::Struct(r#struct) => Or::L(precondition_struct(r#struct)),
@@ -20,7 +25,8 @@ pub(crate) fn precondition(item: &ir::Item) -> TokenStream {
             r#enum
                 .variants
                 .iter()
-                .flat_map(|variant| precondition_struct(&variant.r#struct)),
+                .flat_map(|variant| precondition_struct(&variant.r#struct))
+                .chain(precondition_enum(r#enum)),
         ),
     };
 
@@ -36,30 +42,25 @@ fn precondition_struct<'ir>(r#struct: &'ir ir::Struct) -> impl Iterator<Item = T
     let fields = r#struct
         .fields
         .iter()
-        .map(|field| &field.r#type)
         // Only need to check user-defined types
-        .filter(|r#type| r#type.is_user());
+        .filter_map(|field| Some((field.r#type.span(), field.r#type.as_user()?)));
 
     let non_zero = fields
         .clone()
-        .filter(|r#type| r#type.is_non_zero())
-        .map(|r#type| {
-            let span = r#type.span();
-            let r#type = r#type.deref();
+        .filter(|(_, r#type)| r#type.is_non_zero())
+        .map(|(span, r#type)| {
             quote_spanned! {span=>
                 ::ribbit::private::assert_non_zero::<#r#type>();
             }
         });
 
-    let pack = fields.map(|r#type| {
+    let pack = fields.map(|(span, r#type)| {
         let assert = match r#type.is_generic() {
             true => quote!(assert_size_ge),
             false => quote!(assert_size_eq),
         };
 
-        let span = r#type.span();
         let size = r#type.size();
-        let r#type = r#type.deref();
         quote_spanned! {span=>
             ::ribbit::private::#assert::<#r#type>(#size);
         }
@@ -67,3 +68,31 @@ fn precondition_struct<'ir>(r#struct: &'ir ir::Struct) -> impl Iterator<Item = T
 
     non_zero.chain(pack)
 }
+
+fn precondition_enum<'ir>(r#enum: &'ir ir::Enum) -> impl Iterator<Item = TokenStream> + 'ir {
+    let size = r#enum.discriminant.size;
+
+    // Literal discriminants are checked by the macro
+    let expressions = (!r#enum.expressions.is_empty()).then_some(r#enum);
+
+    let unique = expressions.map(|r#enum| {
+        let discriminants = r#enum
+            .variants
+            .iter()
+            .filter(|variant| !variant.is_unknown())
+            .map(|variant| r#enum.discriminant_value(variant));
+        quote!(::ribbit::private::assert_discriminants(&[#(#discriminants),*], #size))
+    });
+
+    let non_zero = expressions
+        .into_iter()
+        .filter(|r#enum| r#enum.tight.is_non_zero())
+        .flat_map(|r#enum| r#enum.variants.iter())
+        .filter(|variant| !variant.is_unknown() && !*variant.opt.non_zero)
+        .map(move |variant| {
+            let discriminant = r#enum.discriminant_value(variant);
+            quote!(::ribbit::private::assert_discriminant_non_zero(#discriminant, #size))
+        });
+
+    unique.into_iter().chain(non_zero)
+}
@@ -0,0 +1,258 @@
+use std::borrow::Cow;
+
+use darling::FromMeta;
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+use quote::ToTokens as _;
+
+use crate::ir;
+use crate::Type;
+
+#[derive(FromMeta, Clone, Debug, Default)]
+pub(crate) struct ItemOpt(pub(crate) ir::CommonOpt);
+
+impl ItemOpt {
+    pub(crate) fn name(&self) -> Cow<'_, syn::Ident> {
+        self.0
+            .rename_with(|| Cow::Owned(format_ident!("try_from_raw")))
+    }
+}
+
+pub(crate) fn try_from_raw(item: &ir::Item) -> TokenStream {
+    let opt = &item.opt().try_from_raw;
+
+    if opt.0.skip {
+        return TokenStream::default();
+    }
+
+    let vis = opt.0.vis(&item.vis);
+    let name = opt.name();
+    let tight = item.tight();
+    let precondition = crate::gen::precondition::assert();
+
+    quote! {
+        #[inline]
+        #vis const fn #name(raw: #tight) -> Result<Self, ::ribbit::InvalidBits> {
+            #precondition
+            ::ribbit::private::try_loose_to_packed::<Self>(::ribbit::convert::packed_to_loose(raw))
+        }
+    }
+}
+
+/// Generate a [`TryFrom`] implementation from the loose type, which
+/// also checks that bits above the size of the packed type are zero.
+pub(crate) fn try_from(item: &ir::Item) -> TokenStream {
+    if item.opt().try_from_raw.0.skip {
+        return TokenStream::default();
+    }
+
+    let generics = item.generics_bounded();
+    let (generics_impl, generics_type, generics_where) = generics.split_for_impl();
+    let packed = item.ident_packed();
+    let loose = item.tight().to_loose();
+
+    quote! {
+        impl #generics_impl ::core::convert::TryFrom<#loose> for #packed #generics_type #generics_where {
+            type Error = ::ribbit::InvalidBits;
+
+            #[inline]
+            fn try_from(loose: #loose) -> Result<Self, Self::Error> {
+                ::ribbit::private::try_loose_to_packed::<Self>(loose)
+            }
+        }
+    }
+}
+
+/// Rules describing the valid bit patterns of the packed type,
+/// evaluated by `ribbit::private::try_loose_to_packed`.
+pub(crate) fn rules(item: &ir::Item) -> TokenStream {
+    let ident = item.ident_unpacked().to_string();
+    let tight = item.tight();
+
+    let non_zero = tight.is_non_zero().then(|| {
+        let size = tight.size();
+        quote!(::ribbit::private::Rule::NonZero {
+            name: #ident,
+            offset: 0,
+            size: #size,
+        })
+    });
+
+    let rules = match &item.data {
+        ir::Data::Struct(r#struct) => {
+            rules_struct(&ident, None, r#struct, 0, vec![false; tight.size()])
+        }
+        ir::Data::Enum(r#enum) => vec![rules_enum(&ident, r#enum)],
+    };
+
+    let rules = non_zero.into_iter().chain(rules);
+    quote!(&[#(#rules),*])
+}
+
+fn rules_enum(ident: &str, r#enum: &ir::Enum) -> TokenStream {
+    let size = r#enum.tight.size();
+    let offset_discriminant = r#enum.discriminant.offset;
+    let size_discriminant = r#enum.discriminant.size;
+    let unknown = r#enum.variants.iter().any(|variant| variant.is_unknown());
+
+    let variants = r#enum
+        .variants
+        .iter()
+        .filter(|variant| !variant.is_unknown())
+        .map(|variant| {
+            let mask = crate::mask(variant.size_discriminant);
+            let discriminant = match variant.discriminant {
+                ir::VariantDiscriminant::Literal(value) => {
+                    proc_macro2::Literal::u128_unsuffixed(value).into_token_stream()
+                }
+                ir::VariantDiscriminant::Expression { .. } => {
+                    let value = r#enum.discriminant_value(variant);
+                    quote!((#value as u128 & #mask))
+                }
+                ir::VariantDiscriminant::Unknown => unreachable!(),
+            };
+
+            let mut occupied = vec![false; size];
+            occupied[offset_discriminant..][..variant.size_discriminant].fill(true);
+
+            let rules = rules_struct(
+                &variant.ident.to_string(),
+                Some(variant.ident),
+                &variant.r#struct,
+                r#enum.offset_payload(variant),
+                occupied,
+            );
+
+            quote! {
+                ::ribbit::private::Variant {
+                    discriminant: #discriminant,
+                    mask: #mask,
+                    rules: &[#(#rules),*],
+                }
+            }
+        });
+
+    quote! {
+        ::ribbit::private::Rule::Enum {
+            name: #ident,
+            offset: #offset_discriminant,
+            size: #size_discriminant,
+            variants: &[#(#variants),*],
+            unknown: #unknown,
+        }
+    }
+}
+
+/// Rules for the fields of `r#struct` placed at `offset`, and the
+/// unused bits not covered by the fields or `occupied`.
+fn rules_struct(
+    ident: &str,
+    variant: Option<&syn::Ident>,
+    r#struct: &ir::Struct,
+    offset: usize,
+    mut occupied: Vec<bool>,
+) -> Vec<TokenStream> {
+    let mut rules = r#struct
+        .iter()
+        .flat_map(|field| {
+            let offset = offset + field.offset;
+            occupied[offset..][..field.r#type.size()].fill(true);
+
+            let name = match variant {
+                None => field.ident.to_token_stream().to_string(),
+                Some(variant) => format!("{}.{}", variant, field.ident.to_token_stream()),
+            };
+
+            rules_field(&name, offset, &field.r#type)
+        })
+        .collect::<Vec<_>>();
+
+    // Runs of unoccupied bits
+    let mut end = 0;
+    while let Some(offset) = occupied[end..].iter().position(|occupied| !occupied) {
+        let offset = end + offset;
+        let size = occupied[offset..]
+            .iter()
+            .take_while(|occupied| !**occupied)
+            .count();
+        end = offset + size;
+
+        rules.push(quote! {
+            ::ribbit::private::Rule::Zero {
+                name: #ident,
+                offset: #offset,
+                size: #size,
+            }
+        });
+    }
+
+    rules
+}
+
+fn rules_field(name: &str, offset: usize, r#type: &Type) -> Vec<TokenStream> {
+    let size = r#type.size();
+
+    match r#type {
+        Type::Tight { tight } if tight.is_non_zero() => vec![quote! {
+            ::ribbit::private::Rule::NonZero {
+                name: #name,
+                offset: #offset,
+                size: #size,
+            }
+        }],
+        Type::Tight { .. } => Vec::new(),
+        Type::User { .. } => {
+            let packed = r#type.packed();
+            vec![quote! {
+                ::ribbit::private::Rule::Field {
+                    name: #name,
+                    offset: #offset,
+                    size: #size,
+                    bits: <#packed as ::ribbit::Unpack>::BITS,
+                    rules: <#packed as ::ribbit::Unpack>::RULES,
+                }
+            }]
+        }
+        // Payload must be zero if the presence bit is not set
+        Type::Option { inner, .. } => {
+            let size_inner = inner.size();
+            let flag = offset + size_inner;
+            let inner = rules_field(name, offset, inner);
+
+            vec![quote! {
+                ::ribbit::private::Rule::Enum {
+                    name: #name,
+                    offset: #flag,
+                    size: 1,
+                    variants: &[
+                        ::ribbit::private::Variant {
+                            discriminant: 0,
+                            mask: 1,
+                            rules: &[::ribbit::private::Rule::Zero {
+                                name: #name,
+                                offset: #offset,
+                                size: #size_inner,
+                            }],
+                        },
+                        ::ribbit::private::Variant {
+                            discriminant: 1,
+                            mask: 1,
+                            rules: &[#(#inner),*],
+                        },
+                    ],
+                    unknown: false,
+                }
+            }]
+        }
+        Type::Array { inner, len, .. } => (0..*len)
+            .flat_map(|index| {
+                rules_field(
+                    &format!("{}[{}]", name, index),
+                    offset + index * inner.size(),
+                    inner,
+                )
+            })
+            .collect(),
+    }
+}
@@ -3,6 +3,7 @@ use quote::quote;
 
 use crate::ir;
 use crate::lift;
+use crate::r#type::Loose;
 
 pub(crate) fn unpack(item: &ir::Item) -> TokenStream {
     let unpacked = item.ident_unpacked();
@@ -16,7 +17,7 @@ pub(crate) fn unpack(item: &ir::Item) -> TokenStream {
                     &r#struct.tight,
                     field,
                     r#struct.max_offset,
-                    field.offset as u8,
+                    field.offset,
                 ));
                 quote!(#unescaped: #value)
             });
@@ -28,40 +29,44 @@ pub(crate) fn unpack(item: &ir::Item) -> TokenStream {
             }
         }
         ir::Data::Enum(r#enum) => {
-            let variants = r#enum.variants.iter().map(|variant| {
+            // Catch-all variant must be matched last
+            let (unknown, known) = r#enum
+                .variants
+                .iter()
+                .partition::<Vec<_>, _>(|variant| variant.is_unknown());
+
+            let variants = known.iter().chain(&unknown).map(|variant| {
                 let fields = variant.r#struct.fields.iter().map(|field| {
                     let name = &field.ident;
-                    let value = field.r#type.unpack(crate::gen::get::get_field(
-                        &r#enum.tight,
-                        field,
-                        r#enum.discriminant.size + variant.r#struct.max_offset,
-                        (r#enum.discriminant.size + field.offset) as u8,
-                    ));
+                    let value = field
+                        .r#type
+                        .unpack(crate::gen::get::get_variant_field(r#enum, variant, field));
 
                     quote!(#name: #value)
                 });
 
-                let discriminant = r#enum
-                    .tight
-                    .to_loose()
-                    .literal(variant.discriminant as u128);
+                let pattern = pattern(r#enum, variant);
 
                 let ident = &variant.ident;
 
-                quote!(#discriminant => #unpacked::#ident { #(#fields ,)* })
+                quote!(#pattern => #unpacked::#ident { #(#fields ,)* })
             });
 
-            let discriminant = lift::Expr::value_self(&r#enum.tight)
-                .and(r#enum.discriminant.mask)
-                .compile(r#enum.tight.to_loose());
+            let discriminant = discriminant(r#enum, quote!(self));
 
-            quote! {
-                match #discriminant {
-                    #(#variants, )*
+            let unreachable = unknown.is_empty().then(|| {
+                quote! {
                     _ => unsafe {
                         ::core::hint::unreachable_unchecked()
                     }
                 }
+            });
+
+            quote! {
+                match #discriminant {
+                    #(#variants, )*
+                    #unreachable
+                }
             }
         }
     };
@@ -73,16 +78,35 @@ pub(crate) fn unpack(item: &ir::Item) -> TokenStream {
     let size = tight.size();
     let loose = tight.to_loose();
 
+    // Fits a tag bit above the packed type, saturating at the largest native integer type
+    let tagged = match loose {
+        _ if size < loose.size() => quote!(#loose),
+        Loose::N8 => quote!(u16),
+        Loose::N16 => quote!(u32),
+        Loose::N32 => quote!(u64),
+        Loose::N64 => quote!(::ribbit::private::Loose128),
+        Loose::N128 | Loose::Words(_) => quote!(#loose),
+    };
+
+    let name = item.ident_unpacked().to_string();
+    let rules = crate::gen::try_from_raw::rules(item);
+    let check_self = check(quote!(self));
+    let check_packed = check(quote!(packed));
+
     quote! {
         unsafe impl #generics_impl ::ribbit::Unpack for #packed #generics_type #generics_where {
             const BITS: usize = #size;
 
             type Unpacked = #unpacked #generics_type;
             type Loose = #loose;
+            type Tagged = #tagged;
             type Raw = #tight;
+            const NAME: &'static str = #name;
+            const RULES: &'static [::ribbit::private::Rule] = #rules;
 
             #[inline]
             fn unpack(self) -> #unpacked #generics_type {
+                #check_self
                 #unpack
             }
 
@@ -93,11 +117,49 @@ pub(crate) fn unpack(item: &ir::Item) -> TokenStream {
 
             #[inline]
             unsafe fn from_raw_unchecked(raw: Self::Raw) -> Self {
-                Self {
+                let packed = Self {
                     value: raw,
                     r#type: ::ribbit::PhantomData,
-                }
+                };
+                #check_packed
+                packed
             }
         }
     }
 }
+
+/// Validate `packed` if the `checked` feature of `ribbit` is enabled.
+pub(crate) fn check(packed: TokenStream) -> TokenStream {
+    quote! {
+        ::ribbit::private::check::<Self>(::ribbit::convert::packed_to_loose(#packed));
+    }
+}
+
+/// Discriminant bits of `packed.value`, to be matched against [`pattern`].
+pub(crate) fn discriminant(r#enum: &ir::Enum, packed: TokenStream) -> TokenStream {
+    lift::Expr::value_tight(quote!(#packed.value), &r#enum.tight)
+        .shift_right(r#enum.discriminant.offset as u8)
+        .and(r#enum.discriminant.mask)
+        .compile(r#enum.tight.to_loose())
+}
+
+/// Pattern matching the [`discriminant`] of `variant`.
+pub(crate) fn pattern(r#enum: &ir::Enum, variant: &ir::Variant) -> TokenStream {
+    let loose = r#enum.tight.to_loose();
+    match variant.discriminant {
+        // Only the low bits of the longest discriminant are significant
+        ir::VariantDiscriminant::Literal(value)
+            if variant.size_discriminant < r#enum.discriminant.size =>
+        {
+            let mask = loose.literal(crate::mask(variant.size_discriminant));
+            let value = loose.literal(value);
+            quote!(discriminant if discriminant & #mask == #value)
+        }
+        ir::VariantDiscriminant::Literal(value) => loose.literal(value),
+        ir::VariantDiscriminant::Expression { .. } => {
+            let value = r#enum.discriminant(variant).compile(loose);
+            quote!(discriminant if discriminant == #value)
+        }
+        ir::VariantDiscriminant::Unknown => quote!(_),
+    }
+}
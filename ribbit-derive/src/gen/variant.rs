@@ -0,0 +1,527 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
+
+use darling::FromMeta;
+use heck::ToSnakeCase as _;
+use proc_macro2::Span;
+use proc_macro2::TokenStream;
+use quote::format_ident;
+use quote::quote;
+
+use crate::error::bail;
+use crate::gen::unpack;
+use crate::ir;
+use crate::lift;
+
+#[derive(FromMeta, Clone, Debug, Default)]
+pub(crate) struct ItemOpt {
+    #[darling(default)]
+    discriminant: ir::CommonOpt,
+    #[darling(default)]
+    variant_count: ir::CommonOpt,
+    #[darling(default)]
+    discriminants: ir::CommonOpt,
+}
+
+impl ItemOpt {
+    fn name_discriminant(&self) -> Cow<'_, syn::Ident> {
+        self.discriminant
+            .rename_with(|| Cow::Owned(format_ident!("discriminant")))
+    }
+
+    fn name_variant_count(&self) -> Cow<'_, syn::Ident> {
+        self.variant_count
+            .rename_with(|| Cow::Owned(format_ident!("VARIANT_COUNT")))
+    }
+
+    fn name_discriminants(&self) -> Cow<'_, syn::Ident> {
+        self.discriminants
+            .rename_with(|| Cow::Owned(format_ident!("DISCRIMINANTS")))
+    }
+}
+
+#[derive(FromMeta, Clone, Debug, Default)]
+pub(crate) struct VariantOpt(ir::CommonOpt);
+
+#[derive(FromMeta, Clone, Debug, Default)]
+pub(crate) struct ViewOpt(ir::CommonOpt);
+
+impl ViewOpt {
+    fn name(&self) -> Cow<'_, syn::Ident> {
+        self.0.rename_with(|| Cow::Owned(format_ident!("view")))
+    }
+}
+
+/// Name of the payload view type for `variant`.
+pub(crate) fn name(item: &ir::Item, variant: &ir::Variant) -> syn::Ident {
+    format_ident!("{}{}", item.ident_packed(), variant.ident)
+}
+
+/// Name of the enum of payload views, unless skipped.
+pub(crate) fn name_view(item: &ir::Item) -> Option<syn::Ident> {
+    match &item.data {
+        _ if item.opt().view.0.skip => None,
+        ir::Data::Enum(_) => Some(format_ident!("{}View", item.ident_unpacked())),
+        ir::Data::Struct(_) => None,
+    }
+}
+
+/// Name of the predicate for `variant`.
+fn is<'ir>(variant: &'ir ir::Variant) -> Cow<'ir, syn::Ident> {
+    variant.is.0.rename_with(|| {
+        Cow::Owned(format_ident!(
+            "is_{}",
+            variant.ident.to_string().to_snake_case()
+        ))
+    })
+}
+
+/// Name of the payload accessor for `variant`.
+fn accessor<'ir>(variant: &'ir ir::Variant) -> Cow<'ir, syn::Ident> {
+    variant
+        .get
+        .0
+        .rename_with(|| Cow::Owned(snake(variant.ident)))
+}
+
+/// Whether `self` holds `variant`, independent of the predicate's options.
+pub(crate) fn matches(r#enum: &ir::Enum, variant: &ir::Variant) -> TokenStream {
+    let discriminant = unpack::discriminant(r#enum, quote!(self));
+    match variant.is_unknown() {
+        true => {
+            let patterns = r#enum
+                .variants
+                .iter()
+                .filter(|variant| !variant.is_unknown())
+                .map(|variant| unpack::pattern(r#enum, variant));
+            quote! {
+                match #discriminant {
+                    #(#patterns => false,)*
+                    _ => true,
+                }
+            }
+        }
+        false => {
+            let pattern = unpack::pattern(r#enum, variant);
+            quote!(matches!(#discriminant, #pattern))
+        }
+    }
+}
+
+/// Check that names generated for enum variants don't collide with
+/// each other or with other associated items of the packed type.
+pub(crate) fn collisions(item: &ir::Item) -> darling::Result<()> {
+    let ir::Data::Enum(r#enum) = &item.data else {
+        return Ok(());
+    };
+
+    let opt = item.opt();
+    let call_site = Span::call_site();
+
+    let bytes = (!opt.bytes.0.skip)
+        .then(|| opt.bytes.names(item))
+        .into_iter()
+        .flatten()
+        .flat_map(|(_, to, try_from)| [Some(to), try_from])
+        .flatten();
+
+    let names = [
+        (!opt.into_raw.0.skip).then(|| opt.into_raw.name()),
+        (!opt.try_from_raw.0.skip).then(|| opt.try_from_raw.name()),
+        (!opt.from_raw_unchecked.0.skip).then(|| opt.from_raw_unchecked.name(None)),
+        (!opt.variants.discriminant.skip).then(|| opt.variants.name_discriminant()),
+        (!opt.variants.variant_count.skip).then(|| opt.variants.name_variant_count()),
+        (!opt.variants.discriminants.skip).then(|| opt.variants.name_discriminants()),
+        (!opt.view.0.skip).then(|| opt.view.name()),
+    ]
+    .into_iter()
+    .flatten()
+    .chain(bytes.map(Cow::Owned))
+    .map(|name| (name, call_site))
+    .chain(r#enum.variants.iter().flat_map(|variant| {
+        let span = variant.ident.span();
+        let fields = variant
+            .r#struct
+            .iter()
+            .filter(|field| !field.r#type.is_zst());
+
+        let get = fields
+            .clone()
+            .filter(|field| !field.opt.get.0.skip)
+            .map(|field| crate::gen::get::FieldOpt::name_variant(variant, field));
+
+        let with = fields
+            .filter(|field| !field.opt.with.0.skip && !variant.is_unknown())
+            .map(|field| crate::gen::with::FieldOpt::name_variant(variant, field));
+
+        [
+            (!opt.new.0.skip && !variant.opt.new.0.skip)
+                .then(|| variant.opt.new.name(Some(variant.ident))),
+            (!variant.opt.from_raw_unchecked.0.skip)
+                .then(|| variant.opt.from_raw_unchecked.name(Some(variant.ident))),
+            (!variant.is.0.skip).then(|| is(variant)),
+            (!variant.get.0.skip && !variant.r#struct.fields.is_empty()).then(|| accessor(variant)),
+        ]
+        .into_iter()
+        .flatten()
+        .chain(get)
+        .chain(with)
+        .map(move |name| (name, span))
+    }));
+
+    let mut seen = HashSet::new();
+    for (name, span) in names {
+        let name = name.to_string();
+        if !seen.insert(name.trim_start_matches("r#").to_owned()) {
+            bail!(span=> crate::Error::NameCollision { name });
+        }
+    }
+
+    Ok(())
+}
+
+/// Variants with a payload view type.
+pub(crate) fn payloads<'ir, 'input>(
+    item: &'ir ir::Item<'input>,
+) -> impl Iterator<Item = &'ir ir::Variant<'input>> + 'ir {
+    let variants = match &item.data {
+        ir::Data::Enum(r#enum) => r#enum.variants.as_slice(),
+        ir::Data::Struct(_) => &[],
+    };
+
+    variants
+        .iter()
+        .filter(|variant| !variant.r#struct.fields.is_empty())
+}
+
+pub(crate) fn views(item: &ir::Item) -> TokenStream {
+    let vis = item.opt().packed.vis_raised(&item.vis);
+    let packed = item.ident_packed();
+
+    let generics = item.generics();
+    let (generics_impl, generics_type, generics_where) = generics.split_for_impl();
+    let (generics_bounded, _, generics_bounded_where) = item.generics_bounded().split_for_impl();
+
+    let lifetimes = generics.lifetimes().collect::<Vec<_>>();
+    let types = generics.type_params().collect::<Vec<_>>();
+
+    let precondition = crate::gen::precondition::assert_of(quote!(#packed #generics_type));
+
+    let view = name_view(item).map(|view| {
+        let variants = match &item.data {
+            ir::Data::Enum(r#enum) => r#enum.variants.as_slice(),
+            ir::Data::Struct(_) => &[],
+        };
+
+        let definitions = variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            match variant.r#struct.fields.is_empty() {
+                true => quote!(#ident),
+                false => {
+                    let payload = name(item, variant);
+                    quote!(#ident(#payload #generics_type))
+                }
+            }
+        });
+
+        let debug = variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let label = ident.to_string();
+            match variant.r#struct.fields.is_empty() {
+                true => quote!(Self::#ident => f.write_str(#label)),
+                false => {
+                    quote!(Self::#ident(payload) => f.debug_tuple(#label).field(&payload).finish())
+                }
+            }
+        });
+
+        let eq = variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            match variant.r#struct.fields.is_empty() {
+                true => quote!((Self::#ident, Self::#ident) => true),
+                false => quote!((Self::#ident(l), Self::#ident(r)) => l == r),
+            }
+        });
+
+        quote! {
+            #vis enum #view #generics_type {
+                #(#definitions,)*
+            }
+
+            #[automatically_derived]
+            impl #generics_impl Copy for #view #generics_type #generics_where {}
+
+            #[automatically_derived]
+            impl #generics_impl Clone for #view #generics_type #generics_where {
+                fn clone(&self) -> Self {
+                    *self
+                }
+            }
+
+            #[automatically_derived]
+            impl #generics_impl ::core::fmt::Debug for #view #generics_type #generics_where {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                    match *self {
+                        #(#debug,)*
+                    }
+                }
+            }
+
+            #[automatically_derived]
+            impl #generics_impl Eq for #view #generics_type #generics_where {}
+
+            #[automatically_derived]
+            impl #generics_impl PartialEq for #view #generics_type #generics_where {
+                #[inline]
+                #[allow(unreachable_patterns)]
+                fn eq(&self, other: &Self) -> bool {
+                    match (*self, *other) {
+                        #(#eq,)*
+                        _ => false,
+                    }
+                }
+            }
+        }
+    });
+
+    payloads(item)
+        .map(|variant| {
+            let view = name(item, variant);
+            let label = view.to_string();
+            let tight = &variant.r#struct.tight;
+            let get = crate::gen::get::get_struct(&variant.r#struct, precondition.clone());
+
+            quote! {
+                #[repr(transparent)]
+                #vis struct #view #generics_type {
+                    value: #tight,
+                    r#type: ::ribbit::PhantomData<fn(#(&#lifetimes ()),*) -> (#(#types),*)>,
+                }
+
+                #[automatically_derived]
+                impl #generics_impl Copy for #view #generics_type #generics_where {}
+
+                #[automatically_derived]
+                impl #generics_impl Clone for #view #generics_type #generics_where {
+                    fn clone(&self) -> Self {
+                        *self
+                    }
+                }
+
+                // Payload is not unpacked, so doesn't require `Debug` on field types
+                #[automatically_derived]
+                impl #generics_impl ::core::fmt::Debug for #view #generics_type #generics_where {
+                    fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        f.debug_tuple(#label).field(&self.value).finish()
+                    }
+                }
+
+                #[automatically_derived]
+                impl #generics_impl Eq for #view #generics_type #generics_where {}
+
+                #[automatically_derived]
+                impl #generics_impl PartialEq for #view #generics_type #generics_where {
+                    #[inline]
+                    fn eq(&self, other: &Self) -> bool {
+                        self.value.eq(&other.value)
+                    }
+                }
+
+                impl #generics_bounded #view #generics_type #generics_bounded_where {
+                    #[inline]
+                    #vis const fn into_raw(self) -> #tight {
+                        #precondition
+                        self.value
+                    }
+
+                    #(#get)*
+                }
+            }
+        })
+        .chain(view)
+        .collect()
+}
+
+pub(crate) fn variant(item: &ir::Item) -> TokenStream {
+    let ir::Data::Enum(r#enum) = &item.data else {
+        return TokenStream::new();
+    };
+
+    let opt = &item.opt().variants;
+    let vis = &item.vis;
+    let loose = r#enum.tight.to_loose();
+    let precondition = crate::gen::precondition::assert();
+    let discriminant = unpack::discriminant(r#enum, quote!(self));
+    let (_, generics_type, _) = item.generics().split_for_impl();
+
+    let known = r#enum
+        .variants
+        .iter()
+        .filter(|variant| !variant.is_unknown());
+
+    let variant_count = (!opt.variant_count.skip).then(|| {
+        let vis = opt.variant_count.vis(vis);
+        let name = opt.name_variant_count();
+        let count = r#enum.variants.len();
+        quote!(#vis const #name: usize = #count;)
+    });
+
+    let discriminants = (!opt.discriminants.skip).then(|| {
+        let vis = opt.discriminants.vis(vis);
+        let name = opt.name_discriminants();
+        let discriminants = known
+            .clone()
+            .map(|variant| r#enum.discriminant(variant).compile(loose))
+            .collect::<Vec<_>>();
+        let len = discriminants.len();
+        quote!(#vis const #name: [#loose; #len] = [#(#discriminants),*];)
+    });
+
+    let discriminant_method = (!opt.discriminant.skip).then(|| {
+        let vis = opt.discriminant.vis(vis);
+        let name = opt.name_discriminant();
+
+        // Bits past a shorter prefix discriminant belong to the payload
+        let prefix = known
+            .clone()
+            .filter(|variant| variant.size_discriminant < r#enum.discriminant.size)
+            .map(|variant| {
+                let pattern = unpack::pattern(r#enum, variant);
+                let value = r#enum.discriminant(variant).compile(loose);
+                quote!(#pattern => #value)
+            });
+
+        quote! {
+            #[inline]
+            #vis const fn #name(self) -> #loose {
+                #precondition
+                match #discriminant {
+                    #(#prefix,)*
+                    discriminant => discriminant,
+                }
+            }
+        }
+    });
+
+    let variants = r#enum.variants.iter().map(|variant| {
+        let matches = matches(r#enum, variant);
+
+        let predicate = (!variant.is.0.skip).then(|| {
+            let vis = variant.is.0.vis(vis);
+            let is = is(variant);
+            quote! {
+                #[inline]
+                #vis const fn #is(self) -> bool {
+                    #precondition
+                    #matches
+                }
+            }
+        });
+
+        let getter = (!variant.get.0.skip && !variant.r#struct.fields.is_empty()).then(|| {
+            let vis = variant.get.0.vis(vis);
+            let get = accessor(variant);
+            let view = name(item, variant);
+            let payload = payload(item, r#enum, variant);
+
+            quote! {
+                #[inline]
+                #vis const fn #get(self) -> Option<#view #generics_type> {
+                    #precondition
+                    match #matches {
+                        true => Some(#payload),
+                        false => None,
+                    }
+                }
+            }
+        });
+
+        quote! {
+            #predicate
+            #getter
+        }
+    });
+
+    let view = name_view(item).map(|view| {
+        // Catch-all variant must be matched last
+        let (unknown, known) = r#enum
+            .variants
+            .iter()
+            .partition::<Vec<_>, _>(|variant| variant.is_unknown());
+
+        let arms = known.iter().chain(&unknown).map(|variant| {
+            let pattern = unpack::pattern(r#enum, variant);
+            let ident = &variant.ident;
+            match variant.r#struct.fields.is_empty() {
+                true => quote!(#pattern => #view::#ident),
+                false => {
+                    let payload = payload(item, r#enum, variant);
+                    quote!(#pattern => #view::#ident(#payload))
+                }
+            }
+        });
+
+        let unreachable = unknown.is_empty().then(|| {
+            quote! {
+                _ => unsafe {
+                    ::core::hint::unreachable_unchecked()
+                }
+            }
+        });
+
+        let opt = &item.opt().view;
+        let vis = opt.0.vis(vis);
+        let name = opt.name();
+
+        quote! {
+            #[inline]
+            #vis const fn #name(self) -> #view #generics_type {
+                #precondition
+                match #discriminant {
+                    #(#arms,)*
+                    #unreachable
+                }
+            }
+        }
+    });
+
+    quote! {
+        #variant_count
+
+        #discriminants
+
+        #discriminant_method
+
+        #(#variants)*
+
+        #view
+    }
+}
+
+/// Payload view of `variant`, assuming `self` holds `variant`.
+fn payload(item: &ir::Item, r#enum: &ir::Enum, variant: &ir::Variant) -> TokenStream {
+    let view = name(item, variant);
+    let value = lift::Expr::value_self(&r#enum.tight)
+        .shift_right(r#enum.offset_payload(variant) as u8)
+        .and(crate::mask(variant.r#struct.tight.size()))
+        .compile(&variant.r#struct.tight);
+
+    quote! {
+        #view {
+            value: #value,
+            r#type: ::ribbit::PhantomData,
+        }
+    }
+}
+
+/// Snake case name of `variant`, escaped if it is a keyword (e.g., `Struct`).
+fn snake(variant: &syn::Ident) -> syn::Ident {
+    let snake = variant.to_string().to_snake_case();
+    match snake.as_str() {
+        "crate" | "self" | "super" => format_ident!("{}_", snake),
+        _ if syn::parse_str::<syn::Ident>(&snake).is_err() => {
+            syn::Ident::new_raw(&snake, variant.span())
+        }
+        _ => format_ident!("{}", snake),
+    }
+}
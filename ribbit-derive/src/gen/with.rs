@@ -2,29 +2,60 @@ use core::iter;
 use std::borrow::Cow;
 
 use darling::FromMeta;
+use heck::ToSnakeCase as _;
 use proc_macro2::TokenStream;
+use quote::format_ident;
 use quote::quote;
 
 use crate::ir;
 use crate::lift;
+use crate::r#type::Loose;
+use crate::r#type::Tight;
 use crate::Or;
+use crate::Type;
 
 #[derive(FromMeta, Clone, Debug, Default)]
-pub(crate) struct FieldOpt(ir::CommonOpt);
+pub(crate) struct FieldOpt(pub(crate) ir::CommonOpt);
 
 impl FieldOpt {
-    fn name<'ir>(field: &'ir ir::Field) -> Cow<'ir, syn::Ident> {
+    pub(crate) fn name<'ir>(field: &'ir ir::Field) -> Cow<'ir, syn::Ident> {
         field
             .opt
             .with
             .0
             .rename_with(|| Cow::Owned(field.ident.prefix("with")))
     }
+
+    /// Name of the updater for the whole value of `field`, which is suffixed
+    /// with `_array` for arrays to make room for the indexed updater.
+    pub(crate) fn name_whole<'ir>(field: &'ir ir::Field) -> Cow<'ir, syn::Ident> {
+        let name = Self::name(field);
+        match field.r#type.as_array() {
+            None => name,
+            Some(_) => Cow::Owned(format_ident!("{}_array", name)),
+        }
+    }
+
+    /// Name of the updater for `field` of enum `variant`.
+    pub(crate) fn name_variant<'ir>(
+        variant: &ir::Variant,
+        field: &'ir ir::Field,
+    ) -> Cow<'ir, syn::Ident> {
+        field.opt.with.0.rename_with(|| {
+            Cow::Owned(format_ident!(
+                "with_{}",
+                field
+                    .ident
+                    .prefix(&variant.ident.to_string().to_snake_case()),
+            ))
+        })
+    }
 }
 
 pub(crate) fn with<'ir>(item: &'ir ir::Item) -> impl Iterator<Item = TokenStream> + 'ir {
-    let ir::Data::Struct(r#struct) = &item.data else {
-        return Or::L(iter::empty());
+    let r#struct = match &item.data {
+        ir::Data::Struct(r#struct) => r#struct,
+        ir::Data::Enum(r#enum) => return Or::L(with_enum(r#enum)),
     };
 
     Or::R(
@@ -32,22 +63,39 @@ pub(crate) fn with<'ir>(item: &'ir ir::Item) -> impl Iterator<Item = TokenStream
             .iter()
             .filter(|field| !field.r#type.is_zst())
             .filter(|field| !field.opt.with.0.skip)
-            .map(move |field| {
-                let value = lift::Expr::or([
-                    lift::Expr::value(field.ident.escape(), &field.r#type)
-                        .shift_left(field.offset as u8),
-                    lift::Expr::value_self(&r#struct.tight)
-                        .and(!(field.r#type.mask() << field.offset) & r#struct.tight.mask()),
-                ])
-                .compile(item.tight());
+            .flat_map(move |field| {
+                let value = match r#struct.tight.is_words() {
+                    true => with_field_words(quote!(self.value), field),
+                    false => lift::Expr::or([
+                        lift::Expr::value(field.ident.escape(), &field.r#type)
+                            .shift_left(field.offset as u8),
+                        lift::Expr::value_self(&r#struct.tight)
+                            .and(!(field.r#type.mask() << field.offset) & r#struct.tight.mask()),
+                    ])
+                    .compile(item.tight()),
+                };
 
                 let vis = field.opt.with.0.vis(&field.vis);
-                let with = FieldOpt::name(field);
+                let with = FieldOpt::name_whole(field);
                 let name = field.ident.escape();
                 let r#type = field.r#type.packed();
                 let precondition = crate::gen::precondition::assert();
 
-                quote! {
+                let element = field.r#type.as_array().map(|(inner, len)| {
+                    let value = with_element(&r#struct.tight, field.offset, inner, len);
+                    let with = FieldOpt::name(field);
+                    let r#type = inner.packed();
+
+                    quote! {
+                        #[inline]
+                        #vis const fn #with(self, index: usize, value: #r#type) -> Self {
+                            #precondition
+                            #value
+                        }
+                    }
+                });
+
+                iter::once(quote! {
                     #[inline]
                     #vis const fn #with(self, #name: #r#type) -> Self {
                         #precondition
@@ -56,7 +104,110 @@ pub(crate) fn with<'ir>(item: &'ir ir::Item) -> impl Iterator<Item = TokenStream
                             r#type: ::ribbit::PhantomData,
                         }
                     }
-                }
+                })
+                .chain(element)
             }),
     )
 }
+
+/// Replace element `index` of an array at `offset` with `value`,
+/// panicking if `index` is out of bounds.
+fn with_element(tight: &Tight, offset: usize, inner: &Type, len: usize) -> TokenStream {
+    let stride = inner.size();
+    let element = inner.convert_to_loose(quote!(value));
+
+    let value = match tight.is_words() {
+        true => {
+            quote!(self.value.with_bits(#offset + index * #stride, #stride, (#element) as u128))
+        }
+        false => {
+            let loose = tight.to_loose();
+            let mask = proc_macro2::Literal::u128_unsuffixed(inner.mask());
+            let old = tight.convert_to_loose(quote!(self.value));
+            let value = tight.convert_from_loose(quote! {
+                ((#old & !(#mask << shift)) | ((((#element) as #loose) & #mask) << shift))
+            });
+
+            quote! {
+                {
+                    let shift = #offset + index * #stride;
+                    #value
+                }
+            }
+        }
+    };
+
+    quote! {
+        assert!(index < #len, "array index out of bounds");
+        Self {
+            value: #value,
+            r#type: ::ribbit::PhantomData,
+        }
+    }
+}
+
+fn with_enum<'ir>(r#enum: &'ir ir::Enum) -> impl Iterator<Item = TokenStream> + 'ir {
+    r#enum
+        .variants
+        .iter()
+        // Updating the catch-all variant's raw value could write a known discriminant
+        .filter(|variant| !variant.is_unknown())
+        .flat_map(move |variant| {
+            let matches = crate::gen::variant::matches(r#enum, variant);
+            let offset = r#enum.offset_payload(variant);
+
+            variant
+                .r#struct
+                .iter()
+                .filter(|field| !field.r#type.is_zst())
+                .filter(|field| !field.opt.with.0.skip)
+                .map(move |field| {
+                    let offset = offset + field.offset;
+                    let value = lift::Expr::or([
+                        lift::Expr::value(field.ident.escape(), &field.r#type)
+                            .shift_left(offset as u8),
+                        lift::Expr::value_self(&r#enum.tight)
+                            .and(!(field.r#type.mask() << offset) & r#enum.tight.mask()),
+                    ])
+                    .compile(&r#enum.tight);
+
+                    let vis = field.opt.with.0.vis(&field.vis);
+                    let with = FieldOpt::name_variant(variant, field);
+                    let name = field.ident.escape();
+                    let r#type = field.r#type.packed();
+                    let precondition = crate::gen::precondition::assert();
+
+                    quote! {
+                        #[inline]
+                        #vis const fn #with(self, #name: #r#type) -> Option<Self> {
+                            #precondition
+                            match #matches {
+                                true => Some(Self {
+                                    value: #value,
+                                    r#type: ::ribbit::PhantomData,
+                                }),
+                                false => None,
+                            }
+                        }
+                    }
+                })
+        })
+}
+
+/// Replace `field` in `words` of a type wider than 128 bits,
+/// where `field` can straddle word boundaries.
+pub(crate) fn with_field_words(words: TokenStream, field: &ir::Field) -> TokenStream {
+    let name = field.ident.escape();
+    let offset = field.offset;
+    let size = field.r#type.size();
+    let loose = field.r#type.to_loose();
+
+    if let Loose::Words(words_field) = loose {
+        let words_field = proc_macro2::Literal::usize_unsuffixed(words_field);
+        let value = field.r#type.convert_to_loose(quote!(#name));
+        return quote!(#words.with_words::<#words_field>(#offset, #size, #value));
+    }
+
+    let value = lift::Expr::value(&*name, &field.r#type).compile(loose);
+    quote!(#words.with_bits(#offset, #size, #value as u128))
+}
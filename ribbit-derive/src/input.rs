@@ -4,6 +4,7 @@ use darling::FromDeriveInput;
 use darling::FromField;
 use darling::FromVariant;
 
+use crate::gen;
 use crate::ir;
 
 #[derive(FromDeriveInput, Debug)]
@@ -22,6 +23,13 @@ pub(crate) struct Item {
 pub(crate) struct Variant {
     #[darling(flatten)]
     pub(crate) opt: ir::VariantOpt,
+    /// Catch-all for unknown discriminants.
+    #[darling(default)]
+    pub(crate) non_exhaustive: SpannedValue<bool>,
+    #[darling(default)]
+    pub(crate) is: gen::variant::VariantOpt,
+    #[darling(default)]
+    pub(crate) get: gen::variant::VariantOpt,
     pub(crate) ident: syn::Ident,
     pub(crate) fields: Fields<SpannedValue<Field>>,
     pub(crate) discriminant: Option<syn::Expr>,
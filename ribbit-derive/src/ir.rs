@@ -1,4 +1,3 @@
-use core::ops::Not as _;
 use std::borrow::Cow;
 
 use darling::usage::GenericsExt;
@@ -15,6 +14,7 @@ use syn::punctuated::Punctuated;
 use crate::error::bail;
 use crate::gen;
 use crate::input;
+use crate::lift;
 use crate::r#type::Tight;
 use crate::Type;
 
@@ -42,14 +42,19 @@ impl<'input> Item<'input> {
                 };
 
                 let tight = match Tight::from_size(*item.opt.non_zero, size) {
+                    Ok(tight) if tight.is_words() => {
+                        bail!(item.opt.size=> crate::Error::Wide { feature: "Enum" })
+                    }
                     Ok(tight) => tight,
                     // FIXME: span
                     Err(error) => bail!(item.opt.size=> error),
                 };
 
-                let mut current_discriminant = 0;
+                let mut expressions = Vec::new();
+                let mut unknown = None;
+                let mut current_discriminant = VariantDiscriminant::Literal(0);
 
-                let variants_ir = variants
+                let mut variants_ir = variants
                     .iter()
                     .map(|variant| {
                         let r#struct = Struct::new(
@@ -59,35 +64,194 @@ impl<'input> Item<'input> {
                             &variant.fields,
                         )?;
 
-                        // FIXME: support arbitrary expression
-                        let discriminant = if let Some(syn::Expr::Lit(syn::ExprLit {
-                            attrs: _,
-                            lit: syn::Lit::Int(int),
-                        })) = &variant.discriminant
-                        {
-                            int.base10_parse()?
-                        } else if variant.discriminant.is_some() {
-                            bail!(variant=> crate::Error::VariantDiscriminant);
-                        } else {
-                            current_discriminant
+                        let discriminant = match &variant.discriminant {
+                            _ if *variant.non_exhaustive => {
+                                if unknown.replace(variant).is_some() {
+                                    bail!(variant.non_exhaustive=> crate::Error::NonExhaustiveDuplicate);
+                                }
+
+                                let field = match variant.fields.len() {
+                                    1 => r#struct.fields.first(),
+                                    _ => None,
+                                };
+
+                                if r#struct.tight != tight
+                                    || field.is_none_or(|field| field.r#type.size() != size)
+                                {
+                                    bail!(variant=> crate::Error::NonExhaustiveVariant { r#enum: tight });
+                                }
+
+                                VariantDiscriminant::Unknown
+                            }
+                            None => current_discriminant,
+                            Some(syn::Expr::Lit(syn::ExprLit {
+                                attrs: _,
+                                lit: syn::Lit::Int(int),
+                            })) => VariantDiscriminant::Literal(int.base10_parse()?),
+                            // Evaluated by the compiler
+                            Some(expression) => {
+                                expressions.push(expression);
+                                VariantDiscriminant::Expression {
+                                    index: expressions.len() - 1,
+                                    offset: 0,
+                                }
+                            }
                         };
 
-                        current_discriminant = discriminant + 1;
+                        current_discriminant = match discriminant {
+                            // Doesn't affect discriminants of subsequent variants
+                            VariantDiscriminant::Unknown => current_discriminant,
+                            discriminant => discriminant.next(),
+                        };
 
                         Ok(Variant {
                             ident: &variant.ident,
                             opt: &variant.opt,
+                            is: &variant.is,
+                            get: &variant.get,
                             discriminant,
+                            // Assigned below
+                            size_discriminant: 0,
                             r#struct,
                         })
                     })
                     .collect::<darling::Result<Vec<_>>>()?;
 
-                if *item.opt.non_zero {
-                    if let Some((variant, span)) = variants_ir
+                let opt_discriminant = &item.opt.discriminant;
+
+                let (size_discriminant, offset_discriminant, niche) = match opt_discriminant.prefix
+                {
+                    true => {
+                        let (size_discriminant, niche) =
+                            prefix(&item.opt.size, variants, &mut variants_ir)?;
+                        (size_discriminant, 0, niche)
+                    }
+                    false => {
+                        // Minimum size that fits values 0..=discriminant
+                        let size_required = expressions.is_empty().then(|| {
+                            variants_ir
+                                .iter()
+                                .filter_map(|variant| match variant.discriminant {
+                                    VariantDiscriminant::Literal(discriminant) => {
+                                        Some(discriminant)
+                                    }
+                                    VariantDiscriminant::Expression { .. }
+                                    | VariantDiscriminant::Unknown => None,
+                                })
+                                .max()
+                                .map(|discriminant| discriminant + 1)
+                                .unwrap_or(0)
+                                .next_power_of_two()
+                                .trailing_zeros() as usize
+                        });
+
+                        let size_discriminant = match (*opt_discriminant.size, size_required) {
+                            (Some(actual), Some(required)) if actual < required => {
+                                bail!(opt_discriminant.size=> crate::Error::DiscriminantSize {
+                                    required,
+                                    actual,
+                                })
+                            }
+                            (Some(actual), _) => actual,
+                            (None, Some(required)) => required,
+                            // Can't evaluate expressions, so reserve all bits
+                            // not used by the largest variant.
+                            (None, None) => size.saturating_sub(
+                                variants_ir
+                                    .iter()
+                                    .filter(|variant| !variant.is_unknown())
+                                    .map(|variant| variant.r#struct.tight.size())
+                                    .max()
+                                    .unwrap_or(0),
+                            ),
+                        };
+
+                        let offset_discriminant = opt_discriminant.offset.unwrap_or(0);
+                        if offset_discriminant + size_discriminant > size {
+                            let error = crate::Error::DiscriminantOverflow {
+                                offset: offset_discriminant,
+                                discriminant: size_discriminant,
+                                r#enum: size,
+                            };
+                            match opt_discriminant.offset.is_some() {
+                                true => bail!(opt_discriminant.offset=> error),
+                                false => bail!(opt_discriminant.size=> error),
+                            }
+                        }
+
+                        for (variant, span) in variants_ir
+                            .iter()
+                            .zip(variants)
+                            .filter(|(variant, _)| !variant.is_unknown())
+                        {
+                            let size_variant = variant.r#struct.tight.size();
+                            match offset_discriminant {
+                                0 if size_variant + size_discriminant > size => {
+                                    bail!(span=> crate::Error::VariantSize {
+                                        variant: size_variant,
+                                        r#enum: size,
+                                        discriminant: size_discriminant,
+                                    })
+                                }
+                                1.. if size_variant > offset_discriminant => {
+                                    bail!(span=> crate::Error::DiscriminantOverlap {
+                                        variant: size_variant,
+                                        offset: offset_discriminant,
+                                    })
+                                }
+                                _ => (),
+                            }
+                        }
+
+                        for variant in &mut variants_ir {
+                            if !variant.is_unknown() {
+                                variant.size_discriminant = size_discriminant;
+                            }
+                        }
+
+                        // Smallest discriminant not used by any variant
+                        let niche = expressions
+                            .is_empty()
+                            .then(|| {
+                                (0..=variants_ir.len() as u128).find(|candidate| {
+                                    variants_ir.iter().all(|variant| {
+                                        variant.discriminant
+                                            != VariantDiscriminant::Literal(*candidate)
+                                    })
+                                })
+                            })
+                            .flatten()
+                            .filter(|niche| *niche <= crate::mask(size_discriminant))
+                            .map(|niche| niche << offset_discriminant);
+
+                        (size_discriminant, offset_discriminant, niche)
+                    }
+                };
+
+                if let Some(unknown) = unknown {
+                    let known = variants_ir
                         .iter()
-                        .zip(variants)
-                        .find(|(variant, _)| variant.discriminant == 0)
+                        .filter(|variant| !variant.is_unknown())
+                        .count() as u128;
+
+                    // Discriminants are unique, so any shortfall leaves one spare
+                    let full = match opt_discriminant.prefix {
+                        true => known > 0 && niche.is_none(),
+                        false => 1u128
+                            .checked_shl(size_discriminant as u32)
+                            .is_some_and(|capacity| known >= capacity),
+                    };
+
+                    if full {
+                        bail!(unknown.non_exhaustive=> crate::Error::NonExhaustiveFull);
+                    }
+                }
+
+                if *item.opt.non_zero {
+                    if let Some((variant, span)) =
+                        variants_ir.iter().zip(variants).find(|(variant, _)| {
+                            variant.discriminant == VariantDiscriminant::Literal(0)
+                        })
                     {
                         if !*variant.opt.non_zero {
                             bail!(span=> crate::Error::VariantNonZero);
@@ -95,34 +259,22 @@ impl<'input> Item<'input> {
                     }
                 }
 
-                let size_discriminant = variants_ir
-                    .iter()
-                    .map(|variant| variant.discriminant)
-                    .max()
-                    // Size must fit values 0..=discriminant
-                    .map(|discriminant| discriminant + 1)
-                    .unwrap_or(0)
-                    .next_power_of_two()
-                    .trailing_zeros() as usize;
-
-                for (variant, span) in variants_ir.iter().zip(variants) {
-                    let size_variant = variant.r#struct.tight.size();
-                    if size_variant + size_discriminant > size {
-                        bail!(span=> crate::Error::VariantSize {
-                            variant: r#variant.r#struct.tight.size(),
-                            r#enum: size,
-                            discriminant: size_discriminant,
-                        });
-                    }
-                }
-
                 let r#enum = Enum {
                     tight,
                     discriminant: Discriminant {
                         size: size_discriminant,
+                        offset: offset_discriminant,
                         mask: crate::mask(size_discriminant),
                     },
+                    // Catch-all variant holds every unused discriminant
+                    niche: niche.filter(|_| unknown.is_none()),
                     variants: variants_ir,
+                    expressions,
+                    unpacked: {
+                        let ident = &item.ident;
+                        let (_, generics_type, _) = item.generics.split_for_impl();
+                        quote!(#ident #generics_type)
+                    },
                 };
 
                 Data::Enum(r#enum)
@@ -136,6 +288,18 @@ impl<'input> Item<'input> {
             .map(Data::Struct)?,
         };
 
+        if let (Data::Enum(_), Some(atomic)) = (&data, &item.opt.derive.atomic) {
+            bail!(atomic=> crate::Error::AtomicEnum);
+        }
+
+        if matches!(&data, Data::Struct(r#struct) if r#struct.tight.is_words())
+            && item.opt.derive.atomic.is_some()
+        {
+            bail!(item.opt.size=> crate::Error::Wide {
+                feature: "derive(Atomic)",
+            });
+        }
+
         Ok(Item {
             opt: &item.opt,
             unpacked: &item.ident,
@@ -184,21 +348,202 @@ pub(crate) struct Enum<'input> {
     pub(crate) discriminant: Discriminant,
     pub(crate) tight: Tight,
     pub(crate) variants: Vec<Variant<'input>>,
+    /// Bit pattern with a discriminant not used by any variant.
+    pub(crate) niche: Option<u128>,
+    /// Discriminant expressions that can only be evaluated by the compiler.
+    pub(crate) expressions: Vec<&'input syn::Expr>,
+    /// Unpacked type with generic parameters, which holds the associated
+    /// constant array that evaluates `expressions`.
+    pub(crate) unpacked: TokenStream,
+}
+
+impl<'input> Enum<'input> {
+    /// Discriminant of `variant` as an `i128` expression.
+    pub(crate) fn discriminant_value(&self, variant: &Variant) -> TokenStream {
+        match variant.discriminant {
+            VariantDiscriminant::Literal(value) => {
+                proc_macro2::Literal::i128_unsuffixed(value as i128).to_token_stream()
+            }
+            VariantDiscriminant::Expression { index, offset } => {
+                let unpacked = &self.unpacked;
+                let index = proc_macro2::Literal::usize_unsuffixed(index);
+                let offset = proc_macro2::Literal::i128_unsuffixed(offset as i128);
+                quote!((<#unpacked>::_RIBBIT_DISCRIMINANTS[#index] + #offset))
+            }
+            VariantDiscriminant::Unknown => {
+                unreachable!("[INTERNAL ERROR]: catch-all variant has no discriminant")
+            }
+        }
+    }
+
+    /// Offset of the payload of `variant`, which is placed below a discriminant
+    /// with a non-zero offset, and above it otherwise.
+    pub(crate) fn offset_payload(&self, variant: &Variant) -> usize {
+        match self.discriminant.offset {
+            0 => variant.size_discriminant,
+            _ => 0,
+        }
+    }
+
+    /// Discriminant of `variant` truncated to the discriminant bits.
+    pub(crate) fn discriminant<'ir>(&'ir self, variant: &Variant) -> lift::Expr<'ir> {
+        match variant.discriminant {
+            VariantDiscriminant::Literal(value) => lift::Expr::constant(value),
+            VariantDiscriminant::Expression { .. } => {
+                lift::Expr::constant_i128(self.discriminant_value(variant))
+                    .and(self.discriminant.mask)
+            }
+            // Discriminant is part of the catch-all variant's payload
+            VariantDiscriminant::Unknown => lift::Expr::constant(0),
+        }
+    }
 }
 
 #[derive(Debug)]
 pub(crate) struct Discriminant {
     pub(crate) size: usize,
+    pub(crate) offset: usize,
     pub(crate) mask: u128,
 }
 
+/// Assign prefix-free discriminants, and return the size of the longest one,
+/// along with an unused discriminant if the code space is not full.
+///
+/// Each discriminant must fit in the bits not used by its variant's payload, so
+/// find the smallest `max` such that discriminants of size `min(available, max)`
+/// satisfy the Kraft inequality. Canonical codes are then assigned in order of
+/// increasing size, and bit-reversed so they can be decoded starting from the
+/// least significant bit.
+fn prefix(
+    size: &SpannedValue<Option<usize>>,
+    variants: &[SpannedValue<input::Variant>],
+    variants_ir: &mut [Variant],
+) -> darling::Result<(usize, Option<u128>)> {
+    let size_enum = size.unwrap_or(0);
+
+    if let Some(discriminant) = variants
+        .iter()
+        .find_map(|variant| variant.discriminant.as_ref())
+    {
+        bail!(discriminant=> crate::Error::PrefixDiscriminant);
+    }
+
+    // Catch-all variant has no discriminant
+    let known = (0..variants_ir.len())
+        .filter(|index| !variants_ir[*index].is_unknown())
+        .collect::<Vec<_>>();
+
+    if known.is_empty() {
+        return Ok((0, None));
+    }
+
+    let available = known
+        .iter()
+        .map(|index| {
+            let size_variant = variants_ir[*index].r#struct.tight.size();
+            match size_enum.checked_sub(size_variant) {
+                Some(available) => Ok(available),
+                None => bail!(variants[*index]=> crate::Error::VariantSize {
+                    variant: size_variant,
+                    r#enum: size_enum,
+                    discriminant: 0,
+                }),
+            }
+        })
+        .collect::<darling::Result<Vec<_>>>()?;
+
+    // Measure code space in units of `2^-max`
+    let Some(max) = (0..=size_enum).find(|max| {
+        available
+            .iter()
+            .try_fold(0u128, |sum, available| {
+                1u128
+                    .checked_shl((max - available.min(max)) as u32)
+                    .and_then(|cost| sum.checked_add(cost))
+            })
+            .is_some_and(|sum| sum - 1 <= crate::mask(*max))
+    }) else {
+        bail!(size=> crate::Error::PrefixSize { r#enum: size_enum });
+    };
+
+    let mut order = (0..known.len()).collect::<Vec<_>>();
+    order.sort_by_key(|index| available[*index].min(max));
+
+    let mut code = 0u128;
+    let mut previous = None;
+
+    for index in order {
+        let size_discriminant = available[index].min(max);
+        let index = known[index];
+
+        if let Some(previous) = previous {
+            code = (code + 1) << (size_discriminant - previous);
+        }
+
+        previous = Some(size_discriminant);
+
+        let variant = &mut variants_ir[index];
+        variant.size_discriminant = size_discriminant;
+        variant.discriminant = VariantDiscriminant::Literal(match size_discriminant {
+            0 => 0,
+            _ => code.reverse_bits() >> (128 - size_discriminant),
+        });
+    }
+
+    // Next canonical code has size `max`, since the longest code does
+    let niche =
+        (max > 0 && code < crate::mask(max)).then(|| (code + 1).reverse_bits() >> (128 - max));
+
+    Ok((max, niche))
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum VariantDiscriminant {
+    Literal(u128),
+    /// Discriminant expression at `index`, plus `offset` for implicit
+    /// discriminants of subsequent variants.
+    Expression {
+        index: usize,
+        offset: usize,
+    },
+    /// Catch-all variant of a non-exhaustive enum, which holds the
+    /// raw value of any unknown discriminant and its payload.
+    Unknown,
+}
+
+impl VariantDiscriminant {
+    fn next(self) -> Self {
+        match self {
+            Self::Literal(value) => Self::Literal(value + 1),
+            Self::Expression { index, offset } => Self::Expression {
+                index,
+                offset: offset + 1,
+            },
+            Self::Unknown => Self::Unknown,
+        }
+    }
+}
+
 pub(crate) struct Variant<'input> {
     pub(crate) opt: &'input VariantOpt,
-    pub(crate) discriminant: usize,
+    /// Predicate `is_{variant}`.
+    pub(crate) is: &'input gen::variant::VariantOpt,
+    /// Payload accessor `{variant}`.
+    pub(crate) get: &'input gen::variant::VariantOpt,
+    pub(crate) discriminant: VariantDiscriminant,
+    /// Size of this variant's discriminant, which only differs
+    /// from the enum's discriminant size for prefix discriminants.
+    pub(crate) size_discriminant: usize,
     pub(crate) ident: &'input syn::Ident,
     pub(crate) r#struct: Struct<'input>,
 }
 
+impl Variant<'_> {
+    pub(crate) fn is_unknown(&self) -> bool {
+        self.discriminant == VariantDiscriminant::Unknown
+    }
+}
+
 pub(crate) struct Struct<'input> {
     pub(crate) max_offset: usize,
     pub(crate) tight: Tight,
@@ -222,14 +567,15 @@ impl Struct<'_> {
             Err(error) => bail!(opt.non_zero=> error),
         };
 
-        let mut bits = 1u128.unbounded_shl(tight.size() as u32).wrapping_sub(1);
+        // Bits not occupied by any field
+        let mut free = vec![true; tight.size()];
         let newtype = fields.len() == 1;
 
         let fields = fields
             .iter()
             .enumerate()
             .map(|(index, field)| {
-                Field::new(opt, type_params, bounds, &mut bits, newtype, index, field)
+                Field::new(opt, type_params, bounds, &mut free, newtype, index, field)
             })
             .collect::<Result<Vec<_>, _>>()?;
 
@@ -237,8 +583,30 @@ impl Struct<'_> {
             bail!(opt.non_zero=> crate::Error::StructNonZero);
         }
 
+        let max_offset = fields.iter().map(|field| field.offset).max().unwrap_or(0);
+
+        // Carry into a guard field must fall off the end of the loose type
+        for field in fields.iter().filter(|field| *field.opt.guard) {
+            if !field.r#type.is_integer()
+                && !matches!(
+                    *field.r#type,
+                    Type::Tight {
+                        tight: Tight::Bool,
+                        ..
+                    }
+                )
+            {
+                bail!(field.opt.guard=> crate::Error::GuardType);
+            }
+
+            let loose = tight.to_loose().size();
+            if field.offset != max_offset || field.offset + field.r#type.size() != loose {
+                bail!(field.opt.guard=> crate::Error::GuardPosition { loose });
+            }
+        }
+
         Ok(Struct {
-            max_offset: fields.iter().map(|field| field.offset).max().unwrap_or(0),
+            max_offset,
             tight,
             fields,
         })
@@ -258,7 +626,17 @@ pub(crate) struct ItemOpt {
     #[darling(default)]
     pub(crate) into_raw: gen::into_raw::ItemOpt,
     #[darling(default)]
+    pub(crate) try_from_raw: gen::try_from_raw::ItemOpt,
+    #[darling(default)]
+    pub(crate) bytes: gen::bytes::ItemOpt,
+    #[darling(default)]
+    pub(crate) variants: gen::variant::ItemOpt,
+    #[darling(default)]
+    pub(crate) view: gen::variant::ViewOpt,
+    #[darling(default)]
     pub(crate) derive: Derive,
+    #[darling(default)]
+    pub(crate) discriminant: DiscriminantOpt,
     pub(crate) forward: Option<Forward>,
 }
 
@@ -281,6 +659,43 @@ pub(crate) struct VariantOpt {
     pub(crate) new: gen::new::VariantOpt,
 }
 
+#[derive(Clone, Debug, Default)]
+pub(crate) struct DiscriminantOpt {
+    pub(crate) size: SpannedValue<Option<usize>>,
+    pub(crate) offset: SpannedValue<Option<usize>>,
+    /// Assign variable-length, prefix-free discriminants.
+    pub(crate) prefix: bool,
+}
+
+impl FromMeta for DiscriminantOpt {
+    fn from_list(items: &[darling::ast::NestedMeta]) -> darling::Result<Self> {
+        #[derive(FromMeta)]
+        struct Layout {
+            #[darling(default)]
+            size: SpannedValue<Option<usize>>,
+            #[darling(default)]
+            offset: SpannedValue<Option<usize>>,
+        }
+
+        let Layout { size, offset } = Layout::from_list(items)?;
+        Ok(Self {
+            size,
+            offset,
+            prefix: false,
+        })
+    }
+
+    fn from_string(value: &str) -> darling::Result<Self> {
+        match value {
+            "prefix" => Ok(Self {
+                prefix: true,
+                ..Default::default()
+            }),
+            _ => Err(darling::Error::unknown_value(value)),
+        }
+    }
+}
+
 #[derive(FromMeta, Clone, Default, Debug)]
 #[darling(rename_all = "PascalCase")]
 pub(crate) struct Derive {
@@ -289,6 +704,7 @@ pub(crate) struct Derive {
     pub(crate) ord: Option<gen::ord::ItemOpt>,
     pub(crate) hash: Option<gen::hash::ItemOpt>,
     pub(crate) from: Option<gen::from::ItemOpt>,
+    pub(crate) atomic: Option<SpannedValue<gen::atomic::ItemOpt>>,
 }
 
 pub(crate) struct Field<'input> {
@@ -304,7 +720,7 @@ impl<'input> Field<'input> {
         opt: &VariantOpt,
         type_params: &darling::usage::IdentSet,
         bounds: &mut Punctuated<syn::WherePredicate, syn::Token![,]>,
-        bits: &mut u128,
+        free: &mut [bool],
         newtype: bool,
         index: usize,
         field: &'input SpannedValue<input::Field>,
@@ -313,18 +729,17 @@ impl<'input> Field<'input> {
         let size = r#type.size();
 
         // Gather trait bounds for generic type parameters
-        if r#type.is_generic() {
-            let r#type = &field.ty;
-            bounds.push(parse_quote!(#r#type: ::ribbit::Pack));
+        if let Some(user) = r#type.as_user().filter(|user| user.is_generic()) {
+            bounds.push(parse_quote!(#user: ::ribbit::Pack));
         }
 
         let offset = match *field.opt.offset {
             None => SpannedValue::new(
-                // First set bit
-                ((*bits as i128) & -(*bits as i128)).trailing_zeros() as usize,
+                // First free bit
+                free.iter().position(|free| *free).unwrap_or(free.len()),
                 field.span(),
             ),
-            Some(offset) => match offset > 128 {
+            Some(offset) => match offset > free.len() {
                 false => SpannedValue::new(offset, field.opt.offset.span()),
                 true => bail!(field => crate::Error::Overflow {
                     offset,
@@ -334,8 +749,8 @@ impl<'input> Field<'input> {
             },
         };
 
-        // Contiguous set bits starting at `offset`
-        let hole = bits.unbounded_shr(*offset as u32).trailing_ones() as usize;
+        // Contiguous free bits starting at `offset`
+        let hole = free[*offset..].iter().take_while(|free| **free).count();
         if hole < size {
             bail!(offset=> crate::Error::Overflow {
                 offset: *offset,
@@ -345,11 +760,7 @@ impl<'input> Field<'input> {
         }
 
         // Remove `size` bits starting at `offset`
-        *bits &= 1u128
-            .unbounded_shl(size as u32)
-            .wrapping_sub(1)
-            .unbounded_shl(*offset as u32)
-            .not();
+        free[*offset..][..size].fill(false);
 
         Ok(Self {
             vis: raise_vis(field.vis.clone()),
@@ -365,6 +776,9 @@ impl<'input> Field<'input> {
 pub(crate) struct FieldOpt {
     #[darling(default)]
     pub(crate) non_zero: SpannedValue<bool>,
+    /// Absorbs carry out of the field below it in generated atomic arithmetic.
+    #[darling(default)]
+    pub(crate) guard: SpannedValue<bool>,
     #[darling(default)]
     pub(crate) size: SpannedValue<Option<usize>>,
     #[darling(default)]
@@ -373,6 +787,15 @@ pub(crate) struct FieldOpt {
     pub(crate) get: gen::get::FieldOpt,
     #[darling(default)]
     pub(crate) with: gen::with::FieldOpt,
+    pub(crate) option: Option<SpannedValue<OptionOpt>>,
+}
+
+/// Encoding of an `Option<T>` field whose packed type has no niche.
+#[derive(FromMeta, Copy, Clone, Debug, PartialEq, Eq)]
+#[darling(rename_all = "snake_case")]
+pub(crate) enum OptionOpt {
+    /// Presence bit above the bits of `T`.
+    Flag,
 }
 
 pub(crate) enum FieldIdent<'input> {
@@ -397,7 +820,7 @@ impl<'input> FieldIdent<'input> {
         }
     }
 
-    pub(crate) fn prefix(&self, prefix: &'static str) -> syn::Ident {
+    pub(crate) fn prefix(&self, prefix: &str) -> syn::Ident {
         match self {
             FieldIdent::Named(named) => format_ident!("{}_{}", prefix, named),
             FieldIdent::Unnamed(unnamed) => {
@@ -33,21 +33,30 @@ pub fn pack(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
 fn pack_impl(input: syn::DeriveInput, output: &mut TokenStream) -> Result<(), darling::Error> {
     let input = input::Item::from_derive_input(&input)?;
     let item = Item::new(&input)?;
+    gen::variant::collisions(&item)?;
 
+    let discriminant = gen::discriminant(&item);
     let precondition = gen::precondition(&item);
     let new = gen::new(&item);
     let pack = gen::pack(&item);
     let packed = gen::packed(&item);
     let unpack = gen::unpack(&item);
+    let variant = gen::variant(&item);
+    let views = gen::variant::views(&item);
     let get = gen::get(&item);
     let with = gen::with(&item);
     let into_raw = gen::into_raw(&item);
     let from_raw_unchecked = gen::from_raw_unchecked(&item);
+    let try_from_raw = gen::try_from_raw(&item);
+    let try_from = gen::try_from(&item);
+    let bytes = gen::bytes(&item);
     let from = gen::from(&item);
     let debug = gen::debug(&item);
     let hash = gen::hash(&item);
     let eq = gen::eq(&item);
     let ord = gen::ord(&item);
+    let atomic = gen::atomic(&item);
+    let niche = gen::niche(&item);
 
     let generics = item.generics_bounded();
     let (generics_impl, generics_type, generics_where) = generics.split_for_impl();
@@ -55,15 +64,27 @@ fn pack_impl(input: syn::DeriveInput, output: &mut TokenStream) -> Result<(), da
     let ident_packed = item.ident_packed();
     let ident_module = format_ident!("{}", item.ident_packed().to_string().to_snake_case());
     let vis_packed = item.opt().packed.vis(&input.vis);
+    let export_atomic = item.opt().derive.atomic.as_ref().map(|_| {
+        let ident_atomic = gen::atomic::name(&item);
+        quote!(#vis_packed use #ident_module::#ident_atomic;)
+    });
+    let export_views = gen::variant::payloads(&item)
+        .map(|variant| gen::variant::name(&item, variant))
+        .chain(gen::variant::name_view(&item))
+        .map(|ident_view| quote!(#vis_packed use #ident_module::#ident_view;));
 
     output.append_all(quote! {
         #pack
 
+        #discriminant
+
         mod #ident_module {
             use super::#ident_unpacked;
 
             #packed
 
+            #views
+
             #unpack
 
             impl #generics_impl #ident_packed #generics_type #generics_where {
@@ -71,24 +92,36 @@ fn pack_impl(input: syn::DeriveInput, output: &mut TokenStream) -> Result<(), da
 
                 #(#new)*
 
+                #variant
+
                 #into_raw
 
                 #(#from_raw_unchecked)*
 
+                #try_from_raw
+
+                #bytes
+
                 #(#get)*
 
                 #(#with)*
             }
 
             #from
+            #try_from
             #debug
 
             #hash
             #eq
             #ord
+
+            #atomic
+            #niche
         }
 
         #vis_packed use #ident_module::#ident_packed;
+        #export_atomic
+        #(#export_views)*
 
     });
 
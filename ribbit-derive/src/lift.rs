@@ -12,6 +12,8 @@ use crate::Type;
 #[derive(Debug)]
 pub(crate) enum Expr<'ir> {
     Constant(u128),
+    /// Constant `i128` expression evaluated by the compiler.
+    ConstantI128(TokenStream),
     Value {
         value: TokenStream,
         r#type: &'ir Type,
@@ -59,6 +61,10 @@ impl<'ir> Expr<'ir> {
         Self::Constant(value)
     }
 
+    pub(crate) fn constant_i128(value: TokenStream) -> Self {
+        Self::ConstantI128(value)
+    }
+
     pub(crate) fn and(self, mask: u128) -> Self {
         Self::And {
             expr: Box::new(self),
@@ -99,7 +105,7 @@ impl<'ir> Expr<'ir> {
 
     fn unify(&self, loose: Loose) -> Loose {
         match self {
-            Expr::Constant(_) => loose,
+            Expr::Constant(_) | Expr::ConstantI128(_) => loose,
             Expr::ValueTight { tight, .. } => tight.to_loose().max(loose),
             Expr::Value { r#type, .. } => r#type.to_loose().max(loose),
             Expr::And { expr, .. } | Expr::Shift { expr, .. } => expr.unify(loose),
@@ -114,6 +120,8 @@ impl<'ir> Expr<'ir> {
     fn compile_intermediate(&self, loose: Loose) -> TokenStream {
         match self {
             Self::Constant(value) => loose.literal(*value),
+            // Casting from `i128` truncates to the two's complement representation
+            Self::ConstantI128(value) => quote!(((#value) as #loose)),
 
             Self::Value { value, r#type } => value.clone().convert(*r#type, loose),
             Self::ValueTight { value, tight } => value.clone().convert(*tight, loose),
@@ -275,18 +283,18 @@ impl Convert for TokenStream {
 
             let value = from.convert_to_loose(value);
 
-            let value = if !from.is_generic() && !into.is_generic() {
+            let value = if !from.is_opaque() && !into.is_opaque() {
                 match from_loose == into_loose {
                     true => value,
                     false => quote!((#value as #into_loose)),
                 }
             } else {
-                let from_loose = match from.is_generic() {
+                let from_loose = match from.is_opaque() {
                     true => quote!(_),
                     false => quote!(#from_loose),
                 };
 
-                let into_loose = match into.is_generic() {
+                let into_loose = match into.is_opaque() {
                     true => quote!(_),
                     false => quote!(#into_loose),
                 };
@@ -313,7 +321,9 @@ impl<'ir> Deref for TypeRef<'ir> {
 
 impl<'ir> From<Loose> for TypeRef<'ir> {
     fn from(loose: Loose) -> Self {
-        Self::from(loose.as_tight())
+        Self(Cow::Owned(Type::Tight {
+            tight: loose.to_tight(),
+        }))
     }
 }
 
@@ -29,6 +29,17 @@ pub(crate) enum Type {
         uses: IdentSet,
         tight: Tight,
     },
+    /// `Option<T>` encoded as a presence bit above the bits of `T`.
+    Option {
+        inner: Box<Type>,
+        tight: Tight,
+    },
+    /// `[T; len]` encoded as `len` consecutive elements, starting from the least significant bits.
+    Array {
+        inner: Box<Type>,
+        len: usize,
+        tight: Tight,
+    },
 }
 
 impl Type {
@@ -39,12 +50,20 @@ impl Type {
         type_params: &IdentSet,
         ty: syn::Type,
     ) -> darling::Result<SpannedValue<Self>> {
-        let syn::Type::Path(path) = ty else {
-            bail!(ty=> Error::UnsupportedType)
+        let path = match ty {
+            syn::Type::Path(path) => path,
+            syn::Type::Array(array) => {
+                return Self::parse_array(newtype, opt_variant, opt_field, type_params, array)
+            }
+            _ => bail!(ty=> Error::UnsupportedType),
         };
 
         let span = path.span();
 
+        if opt_field.option.is_some() {
+            return Self::parse_option(newtype, opt_variant, opt_field, type_params, path);
+        }
+
         if let Some(tight) = Tight::from_path(&path) {
             if let Some(expected) = opt_field.size.filter(|size| *size != tight.size()) {
                 bail!(span=> Error::WrongSize {
@@ -85,21 +104,174 @@ impl Type {
         Ok(SpannedValue::new(Self::User { path, uses, tight }, span))
     }
 
+    fn parse_option(
+        newtype: bool,
+        opt_variant: &ir::VariantOpt,
+        opt_field: &ir::FieldOpt,
+        type_params: &IdentSet,
+        path: TypePath,
+    ) -> darling::Result<SpannedValue<Self>> {
+        let span = path.span();
+
+        let inner = match path.path.segments.last() {
+            Some(syn::PathSegment {
+                ident,
+                arguments: syn::PathArguments::AngleBracketed(arguments),
+            }) if ident == "Option" && arguments.args.len() == 1 => match &arguments.args[0] {
+                syn::GenericArgument::Type(inner) => inner.clone(),
+                _ => bail!(span=> Error::OptionType),
+            },
+            _ => bail!(span=> Error::OptionType),
+        };
+
+        let size = match (newtype, *opt_field.size) {
+            (false, size) | (true, size @ Some(_)) => size,
+            (true, None) => *opt_variant.size,
+        };
+
+        // Size attribute includes the presence bit
+        let opt_inner = ir::FieldOpt {
+            size: SpannedValue::new(
+                size.map(|size| size.saturating_sub(1)),
+                opt_field.size.span(),
+            ),
+            option: None,
+            ..opt_field.clone()
+        };
+
+        let inner = Self::parse(false, opt_variant, &opt_inner, type_params, inner)?;
+
+        let tight = match Tight::from_size(false, inner.size() + 1) {
+            Ok(tight) if tight.is_words() => bail!(span=> Error::Wide {
+                feature: "#[ribbit(option = \"flag\")]",
+            }),
+            Ok(tight) => tight,
+            Err(error) => bail!(span=> error),
+        };
+
+        Ok(SpannedValue::new(
+            Self::Option {
+                inner: Box::new(inner.into_inner()),
+                tight,
+            },
+            span,
+        ))
+    }
+
+    fn parse_array(
+        newtype: bool,
+        opt_variant: &ir::VariantOpt,
+        opt_field: &ir::FieldOpt,
+        type_params: &IdentSet,
+        array: syn::TypeArray,
+    ) -> darling::Result<SpannedValue<Self>> {
+        let span = array.span();
+
+        let len = match &array.len {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(len),
+                ..
+            }) => len.base10_parse::<usize>()?,
+            len => bail!(len=> Error::ArrayLength),
+        };
+
+        if len == 0 {
+            bail!(array.len=> Error::ArrayLength);
+        }
+
+        let size = match (newtype, *opt_field.size) {
+            (false, size) | (true, size @ Some(_)) => size,
+            (true, None) => *opt_variant.size,
+        };
+
+        // Size attribute covers the whole array
+        let size_inner = match size {
+            None => None,
+            Some(size) if size % len == 0 => Some(size / len),
+            Some(size) => bail!(opt_field.size=> Error::ArraySize { size, len }),
+        };
+
+        let opt_inner = ir::FieldOpt {
+            size: SpannedValue::new(size_inner, opt_field.size.span()),
+            ..opt_field.clone()
+        };
+
+        let inner = Self::parse(false, opt_variant, &opt_inner, type_params, *array.elem)?;
+
+        let tight = match Tight::from_size(false, inner.size() * len) {
+            Ok(tight) if tight.is_words() => bail!(span=> Error::Wide { feature: "Array" }),
+            Ok(tight) => tight,
+            Err(error) => bail!(span=> error),
+        };
+
+        Ok(SpannedValue::new(
+            Self::Array {
+                inner: Box::new(inner.into_inner()),
+                len,
+                tight,
+            },
+            span,
+        ))
+    }
+
     pub(crate) fn is_user(&self) -> bool {
-        matches!(self, Self::User { .. })
+        !matches!(self, Self::Tight { .. })
     }
 
     pub(crate) fn is_generic(&self) -> bool {
-        matches!(self, Self::User { uses, .. } if !uses.is_empty())
+        match self {
+            Self::Tight { .. } => false,
+            Self::User { uses, .. } => !uses.is_empty(),
+            Self::Option { inner, .. } | Self::Array { inner, .. } => inner.is_generic(),
+        }
+    }
+
+    /// Whether the loose type of the packed representation is only known to the
+    /// compiler: generic types, and `Result<T, E>` or `Either<L, R>`, whose loose type
+    /// can be wider than `size` (e.g., with a nested `Either` payload).
+    pub(crate) fn is_opaque(&self) -> bool {
+        match self {
+            Self::User { path, .. } => {
+                self.is_generic()
+                    || path.path.segments.last().is_some_and(|segment| {
+                        segment.ident == "Result" || segment.ident == "Either"
+                    })
+            }
+            _ => self.is_generic(),
+        }
+    }
+
+    /// User-defined type that must be checked against its size attribute.
+    pub(crate) fn as_user(&self) -> Option<&Self> {
+        match self {
+            Self::Tight { .. } => None,
+            Self::User { .. } => Some(self),
+            Self::Option { inner, .. } | Self::Array { inner, .. } => inner.as_user(),
+        }
+    }
+
+    /// Element type and length of an array.
+    pub(crate) fn as_array(&self) -> Option<(&Self, usize)> {
+        match self {
+            Self::Array { inner, len, .. } => Some((inner, *len)),
+            _ => None,
+        }
     }
 
     pub(crate) fn is_loose(&self) -> bool {
         matches!(self, Self::Tight { tight, .. } if tight.is_loose())
     }
 
+    pub(crate) fn is_integer(&self) -> bool {
+        matches!(self, Self::Tight { tight, .. } if tight.is_integer())
+    }
+
     pub(crate) fn as_tight(&self) -> &Tight {
         match self {
-            Self::Tight { tight, .. } | Self::User { tight, .. } => tight,
+            Self::Tight { tight, .. }
+            | Self::User { tight, .. }
+            | Self::Option { tight, .. }
+            | Self::Array { tight, .. } => tight,
         }
     }
 
@@ -111,6 +283,15 @@ impl Type {
         match self {
             Type::User { .. } => quote!(<#self as ::ribbit::Pack>::Packed),
             Type::Tight { .. } => quote!(#self),
+            Type::Option { inner, .. } => {
+                let inner = inner.packed();
+                quote!(Option<#inner>)
+            }
+            Type::Array { inner, len, .. } => {
+                let inner = inner.packed();
+                let len = proc_macro2::Literal::usize_unsuffixed(*len);
+                quote!([#inner; #len])
+            }
         }
     }
 
@@ -118,6 +299,25 @@ impl Type {
         match self {
             Type::User { .. } => quote!(#expression.pack()),
             Type::Tight { .. } => expression,
+            Type::Option { inner, .. } => {
+                let inner = inner.pack(quote!(inner));
+                quote! {
+                    match #expression {
+                        Some(inner) => Some(#inner),
+                        None => None,
+                    }
+                }
+            }
+            Type::Array { inner, .. } if !inner.is_user() => expression,
+            Type::Array { inner, len, .. } => {
+                let elements = (0..*len).map(|index| inner.pack(quote!(array[#index])));
+                quote! {
+                    {
+                        let array = #expression;
+                        [#(#elements),*]
+                    }
+                }
+            }
         }
     }
 
@@ -125,13 +325,36 @@ impl Type {
         match self {
             Type::User { .. } => quote!(#expression.unpack()),
             Type::Tight { .. } => expression,
+            Type::Option { inner, .. } => {
+                let inner = inner.unpack(quote!(inner));
+                quote! {
+                    match #expression {
+                        Some(inner) => Some(#inner),
+                        None => None,
+                    }
+                }
+            }
+            Type::Array { inner, .. } if !inner.is_user() => expression,
+            Type::Array { inner, len, .. } => {
+                let elements = (0..*len).map(|index| inner.unpack(quote!(array[#index])));
+                quote! {
+                    {
+                        let array = #expression;
+                        [#(#elements),*]
+                    }
+                }
+            }
         }
     }
 
     pub(crate) fn convert_to_loose(&self, expression: TokenStream) -> TokenStream {
         match self {
             Type::Tight { tight, .. } => tight.convert_to_loose(expression),
-            Type::User { .. } if self.is_generic() => {
+            // Wide types have the same loose type as their size
+            Type::User { tight, .. } if tight.is_words() => {
+                quote!(::ribbit::convert::packed_to_loose(#expression))
+            }
+            Type::User { .. } if self.is_opaque() => {
                 let loose = self.to_loose();
                 quote! {
                     ::ribbit::convert::loose_to_loose::<_, #loose>(
@@ -142,6 +365,34 @@ impl Type {
             Type::User { .. } => {
                 quote!(::ribbit::convert::packed_to_loose(#expression))
             }
+            Type::Option { inner, tight } => {
+                let loose = tight.to_loose();
+                let value = inner.convert_to_loose(quote!(inner));
+                let flag = proc_macro2::Literal::usize_unsuffixed(inner.size());
+                quote! {
+                    match #expression {
+                        Some(inner) => (#value as #loose) | (1 << #flag),
+                        None => 0,
+                    }
+                }
+            }
+            Type::Array { inner, len, tight } => {
+                let loose = tight.to_loose();
+                let mask = proc_macro2::Literal::u128_unsuffixed(inner.mask());
+                let elements = (0..*len).map(|index| {
+                    let value = inner.convert_to_loose(quote!(array[#index]));
+                    let shift = proc_macro2::Literal::usize_unsuffixed(index * inner.size());
+
+                    // Mask off sign extension of signed elements
+                    quote!((((#value) as #loose) & #mask) << #shift)
+                });
+                quote! {
+                    {
+                        let array = #expression;
+                        #(#elements)|*
+                    }
+                }
+            }
         }
     }
 
@@ -152,9 +403,49 @@ impl Type {
                 let packed = self.packed();
                 quote!(unsafe { ::ribbit::convert::loose_to_packed::<#packed>(#expression) })
             }
+            Type::Option { inner, tight } => {
+                let loose = tight.to_loose();
+                let value = inner.convert_from_bits(quote!(loose));
+                let flag = proc_macro2::Literal::usize_unsuffixed(inner.size());
+                quote! {
+                    {
+                        let loose: #loose = #expression;
+                        match (loose >> #flag) & 1 {
+                            0 => None,
+                            _ => Some(#value),
+                        }
+                    }
+                }
+            }
+            Type::Array { inner, len, tight } => {
+                let loose = tight.to_loose();
+                let elements = (0..*len).map(|index| {
+                    let shift = proc_macro2::Literal::usize_unsuffixed(index * inner.size());
+                    inner.convert_from_bits(quote!((loose >> #shift)))
+                });
+                quote! {
+                    {
+                        let loose: #loose = #expression;
+                        [#(#elements),*]
+                    }
+                }
+            }
         }
     }
 
+    /// Convert the low bits of native integer `expression` into `self`, ignoring higher bits.
+    pub(crate) fn convert_from_bits(&self, expression: TokenStream) -> TokenStream {
+        let loose = self.to_loose();
+        let mask = proc_macro2::Literal::u128_unsuffixed(self.mask());
+        let value = match self.is_opaque() {
+            true => quote! {
+                ::ribbit::convert::loose_to_loose::<#loose, _>((#expression & #mask) as #loose)
+            },
+            false => quote!(((#expression & #mask) as #loose)),
+        };
+        self.convert_from_loose(value)
+    }
+
     pub(crate) fn size(&self) -> usize {
         self.as_tight().size()
     }
@@ -177,6 +468,11 @@ impl ToTokens for Type {
         match self {
             Self::Tight { tight } => tight.to_tokens(tokens),
             Self::User { path, .. } => path.to_tokens(tokens),
+            Self::Option { inner, .. } => quote!(Option<#inner>).to_tokens(tokens),
+            Self::Array { inner, len, .. } => {
+                let len = proc_macro2::Literal::usize_unsuffixed(*len);
+                quote!([#inner; #len]).to_tokens(tokens)
+            }
         }
     }
 }
@@ -186,6 +482,19 @@ impl PartialEq for Type {
         match (self, other) {
             (Type::Tight { tight: left }, Type::Tight { tight: right }) => left == right,
             (Type::User { path: left, .. }, Type::User { path: right, .. }) => left == right,
+            (Type::Option { inner: left, .. }, Type::Option { inner: right, .. }) => left == right,
+            (
+                Type::Array {
+                    inner: left,
+                    len: len_left,
+                    ..
+                },
+                Type::Array {
+                    inner: right,
+                    len: len_right,
+                    ..
+                },
+            ) => left == right && len_left == len_right,
             _ => false,
         }
     }
@@ -102,14 +102,19 @@ impl Arbitrary {
         }
 
         if self.signed {
-            let loose = self.to_loose();
-            quote!((#expression.value() as #loose))
+            // Bits without sign extension
+            quote!(#expression.to_bits())
         } else {
             quote!(#expression.value())
         }
     }
 
     pub(crate) fn convert_from_loose(&self, expression: TokenStream) -> TokenStream {
+        if self.signed && !self.non_zero && !self.is_loose() {
+            // Sign extend into the underlying native type
+            return quote!(#self::from_bits(#expression));
+        }
+
         if self.non_zero || !self.is_loose() {
             // Skip validation in non-zero and arbitrary-int constructors
             return quote!(unsafe { ::ribbit::convert::loose_to_packed::<#self>(#expression) });
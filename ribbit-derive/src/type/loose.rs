@@ -15,6 +15,8 @@ pub(crate) enum Loose {
     N32,
     N64,
     N128,
+    /// Array of `u64` words, for types wider than 128 bits.
+    Words(usize),
 }
 
 impl Loose {
@@ -38,6 +40,7 @@ impl Loose {
             Self::N32 => 32,
             Self::N64 => 64,
             Self::N128 => 128,
+            Self::Words(words) => words * 64,
         }
     }
 
@@ -46,13 +49,14 @@ impl Loose {
         TokenStream::from_str(&format!("{value:#X}{self}")).unwrap()
     }
 
-    pub(crate) fn as_tight(&self) -> &'static Tight {
+    pub(crate) fn to_tight(self) -> Tight {
         match self {
-            Loose::N8 => &Tight::Arbitrary(Arbitrary::N8),
-            Loose::N16 => &Tight::Arbitrary(Arbitrary::N16),
-            Loose::N32 => &Tight::Arbitrary(Arbitrary::N32),
-            Loose::N64 => &Tight::Arbitrary(Arbitrary::N64),
-            Loose::N128 => &Tight::Arbitrary(Arbitrary::N128),
+            Loose::N8 => Tight::Arbitrary(Arbitrary::N8),
+            Loose::N16 => Tight::Arbitrary(Arbitrary::N16),
+            Loose::N32 => Tight::Arbitrary(Arbitrary::N32),
+            Loose::N64 => Tight::Arbitrary(Arbitrary::N64),
+            Loose::N128 => Tight::Arbitrary(Arbitrary::N128),
+            Loose::Words(words) => Tight::Words(words * 64),
         }
     }
 }
@@ -65,6 +69,10 @@ impl ToTokens for Loose {
             Self::N32 => quote!(u32),
             Self::N64 => quote!(u64),
             Self::N128 => quote!(u128),
+            Self::Words(words) => {
+                let words = proc_macro2::Literal::usize_unsuffixed(*words);
+                quote!(::ribbit::Words<#words>)
+            }
         }
         .to_tokens(tokens)
     }
@@ -78,6 +86,7 @@ impl Display for Loose {
             Loose::N32 => "u32",
             Loose::N64 => "u64",
             Loose::N128 => "u128",
+            Loose::Words(words) => return write!(f, "Words<{words}>"),
         };
 
         write!(f, "{name}")
@@ -13,11 +13,19 @@ pub(crate) enum Tight {
     PhantomData,
     Bool,
     Arbitrary(Arbitrary),
+    /// Types wider than 128 bits, backed by an array of `u64` words.
+    Words(usize),
 }
 
 impl Tight {
     pub(crate) fn from_size(non_zero: bool, size: usize) -> Result<Self, crate::Error> {
-        Self::new(non_zero, false, size)
+        match size {
+            129..=512 if non_zero => Err(crate::Error::Wide {
+                feature: "#[ribbit(non_zero)]",
+            }),
+            129..=512 => Ok(Self::Words(size)),
+            _ => Self::new(non_zero, false, size),
+        }
     }
 
     pub(crate) fn from_path(path: &syn::TypePath) -> Option<Self> {
@@ -65,14 +73,20 @@ impl Tight {
             Tight::Unit | Tight::PhantomData => 0,
             Tight::Bool => 1,
             Tight::Arbitrary(arbitrary) => arbitrary.size(),
+            Tight::Words(size) => *size,
         }
     }
 
+    pub(crate) fn is_words(&self) -> bool {
+        matches!(self, Self::Words(_))
+    }
+
     pub(crate) fn mask(&self) -> u128 {
         match self {
             Tight::Unit | Tight::PhantomData => 0,
             Tight::Bool => 1,
             Tight::Arbitrary(arbitrary) => arbitrary.mask(),
+            Tight::Words(size) => crate::mask(*size),
         }
     }
 
@@ -84,10 +98,16 @@ impl Tight {
         matches!(self, Self::Arbitrary(arbitrary) if arbitrary.is_loose())
     }
 
+    /// Integer type that supports wrapping arithmetic.
+    pub(crate) fn is_integer(&self) -> bool {
+        matches!(self, Self::Arbitrary(arbitrary) if !arbitrary.is_non_zero())
+    }
+
     pub(crate) fn to_loose(self) -> Loose {
         match self {
             Tight::Unit | Tight::PhantomData | Tight::Bool => Loose::N8,
             Tight::Arbitrary(arbitrary) => arbitrary.to_loose(),
+            Tight::Words(size) => Loose::Words(size.div_ceil(64)),
         }
     }
 
@@ -107,6 +127,7 @@ impl Tight {
                 }
             }
             Tight::Arbitrary(arbitrary) => arbitrary.convert_to_loose(expression),
+            Tight::Words(_) => expression,
         }
     }
 
@@ -119,6 +140,7 @@ impl Tight {
                 quote!((#expression != #zero))
             }
             Tight::Arbitrary(arbitrary) => arbitrary.convert_from_loose(expression),
+            Tight::Words(_) => expression,
         }
     }
 }
@@ -130,6 +152,7 @@ impl ToTokens for Tight {
             Tight::PhantomData => return quote!(::ribbit::PhantomData).to_tokens(tokens),
             Tight::Bool => quote!(bool),
             Tight::Arbitrary(arbitrary) => return arbitrary.to_tokens(tokens),
+            Tight::Words(_) => return self.to_loose().to_tokens(tokens),
         };
 
         quote!(::ribbit::#path).to_tokens(tokens)
@@ -143,6 +166,7 @@ impl Display for Tight {
             Tight::PhantomData => "PhantomData".fmt(f),
             Tight::Bool => "bool".fmt(f),
             Tight::Arbitrary(arbitrary) => arbitrary.fmt(f),
+            Tight::Words(_) => self.to_loose().fmt(f),
         }
     }
 }
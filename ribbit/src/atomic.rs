@@ -9,24 +9,47 @@ use crate::Loose;
 use crate::Pack;
 use crate::Unpack;
 
+#[cfg(not(all(loom, feature = "loom")))]
 #[doc(no_inline)]
 pub use core::sync::atomic::AtomicU16;
+#[cfg(not(all(loom, feature = "loom")))]
 #[doc(no_inline)]
 pub use core::sync::atomic::AtomicU32;
+#[cfg(not(all(loom, feature = "loom")))]
 #[doc(no_inline)]
 pub use core::sync::atomic::AtomicU64;
+#[cfg(not(all(loom, feature = "loom")))]
 #[doc(no_inline)]
 pub use core::sync::atomic::AtomicU8;
+#[cfg(all(loom, feature = "loom"))]
+#[doc(no_inline)]
+pub use loom::sync::atomic::AtomicU16;
+#[cfg(all(loom, feature = "loom"))]
+#[doc(no_inline)]
+pub use loom::sync::atomic::AtomicU32;
+#[cfg(all(loom, feature = "loom"))]
+#[doc(no_inline)]
+pub use loom::sync::atomic::AtomicU64;
+#[cfg(all(loom, feature = "loom"))]
+#[doc(no_inline)]
+pub use loom::sync::atomic::AtomicU8;
 #[cfg(feature = "u128")]
 #[doc(no_inline)]
 pub use portable_atomic::AtomicU128;
 
+#[cfg(feature = "critical-section")]
+pub mod critical_section;
+#[cfg(feature = "std")]
+mod wait;
+
 /// Type-safe atomic wrapper for unpacked type implementing [`Pack`].
 ///
 /// Generic type parameter `R` defaults to standard library and `portable_atomic`
-/// atomic integer types, but can be overridden.
+/// atomic integer types (or `loom` atomic integer types when compiled with
+/// `--cfg loom` and the `loom` feature), but can be overridden: for example,
+/// `Atomic<U, critical_section::AtomicU32>` with the `critical-section` feature.
 #[repr(transparent)]
-pub struct Atomic<U, R = <<<U as Pack>::Packed as Unpack>::Loose as Loose>::Atomic> {
+pub struct Atomic<U, R = <<<U as Pack>::Packed as Unpack>::Loose as HasAtomic>::Atomic> {
     raw: R,
     unpacked: PhantomData<U>,
 }
@@ -65,6 +88,20 @@ where
         Self::from_raw(R::new_(packed_to_loose(packed)))
     }
 
+    /// Equivalent to [`core::sync::atomic::AtomicU64::into_inner`].
+    #[inline]
+    pub fn into_inner(self) -> U {
+        self.into_packed().unpack()
+    }
+
+    /// Equivalent to [`core::sync::atomic::AtomicU64::into_inner`], but does not unpack.
+    #[inline]
+    pub fn into_packed(mut self) -> U::Packed {
+        let raw = R::with_mut_(&mut self.raw, |raw| *raw);
+        // SAFETY: API inductively preserves packed type invariants
+        unsafe { loose_to_packed(raw) }
+    }
+
     /// Equivalent to [`core::sync::atomic::AtomicU64::load`].
     #[inline]
     pub fn load(&self, ordering: Ordering) -> U {
@@ -97,7 +134,9 @@ where
     /// Also see [`Atomic::set`] and [`Atomic::get_mut_packed`].
     #[inline]
     pub fn get(&mut self) -> U {
-        self.get_mut_packed().unpack()
+        let raw = R::with_mut_(&mut self.raw, |raw| *raw);
+        // SAFETY: API inductively preserves packed type invariants
+        unsafe { loose_to_packed::<U::Packed>(raw) }.unpack()
     }
 
     /// Like [`core::sync::atomic::AtomicU64::get_mut`], but stores a copy.
@@ -106,30 +145,8 @@ where
     /// Also see [`Atomic::get`] and [`Atomic::get_mut_packed`].
     #[inline]
     pub fn set(&mut self, value: U) {
-        *self.get_mut_packed() = value.pack();
-    }
-
-    /// Like [`core::sync::atomic::AtomicU64::get_mut`], but does not unpack.
-    #[inline]
-    pub fn get_mut_packed(&mut self) -> &mut U::Packed {
-        const {
-            assert!(
-                core::mem::size_of::<<crate::Packed<U> as Unpack>::Loose>()
-                    == core::mem::size_of::<U::Packed>()
-            );
-
-            assert!(
-                core::mem::align_of::<<crate::Packed<U> as Unpack>::Loose>()
-                    == core::mem::align_of::<U::Packed>()
-            );
-        }
-
-        // SAFETY: checked above that referenced types have same layout
-        unsafe {
-            core::mem::transmute::<&mut <<U as Pack>::Packed as Unpack>::Loose, &mut U::Packed>(
-                R::get_mut_(&mut self.raw),
-            )
-        }
+        let value = packed_to_loose(value.pack());
+        R::with_mut_(&mut self.raw, |raw| *raw = value)
     }
 
     /// Equivalent to [`core::sync::atomic::AtomicU64::compare_exchange`].
@@ -218,8 +235,318 @@ where
         // SAFETY: API inductively preserves packed type invariants
         unsafe { loose_to_packed(raw) }
     }
+
+    /// Equivalent to [`core::sync::atomic::AtomicU64::fetch_and`], but takes a loose
+    /// value and does not unpack.
+    ///
+    /// # Safety
+    ///
+    /// Caller must guarantee that the result of the operation is a valid bit pattern
+    /// for the packed type.
+    #[inline]
+    pub unsafe fn fetch_and_loose(
+        &self,
+        value: <U::Packed as Unpack>::Loose,
+        ordering: Ordering,
+    ) -> U::Packed {
+        let raw = R::fetch_and_(&self.raw, value, ordering);
+        // SAFETY: caller guarantees packed type invariants
+        unsafe { loose_to_packed(raw) }
+    }
+
+    /// Equivalent to [`core::sync::atomic::AtomicU64::fetch_or`], but takes a loose
+    /// value and does not unpack.
+    ///
+    /// # Safety
+    ///
+    /// Caller must guarantee that the result of the operation is a valid bit pattern
+    /// for the packed type.
+    #[inline]
+    pub unsafe fn fetch_or_loose(
+        &self,
+        value: <U::Packed as Unpack>::Loose,
+        ordering: Ordering,
+    ) -> U::Packed {
+        let raw = R::fetch_or_(&self.raw, value, ordering);
+        // SAFETY: caller guarantees packed type invariants
+        unsafe { loose_to_packed(raw) }
+    }
+
+    /// Equivalent to [`core::sync::atomic::AtomicU64::fetch_xor`], but takes a loose
+    /// value and does not unpack.
+    ///
+    /// # Safety
+    ///
+    /// Caller must guarantee that the result of the operation is a valid bit pattern
+    /// for the packed type.
+    #[inline]
+    pub unsafe fn fetch_xor_loose(
+        &self,
+        value: <U::Packed as Unpack>::Loose,
+        ordering: Ordering,
+    ) -> U::Packed {
+        let raw = R::fetch_xor_(&self.raw, value, ordering);
+        // SAFETY: caller guarantees packed type invariants
+        unsafe { loose_to_packed(raw) }
+    }
+
+    /// Equivalent to [`core::sync::atomic::AtomicU64::fetch_add`], but takes a loose
+    /// value and does not unpack.
+    ///
+    /// # Safety
+    ///
+    /// Caller must guarantee that the result of the operation is a valid bit pattern
+    /// for the packed type.
+    #[inline]
+    pub unsafe fn fetch_add_loose(
+        &self,
+        value: <U::Packed as Unpack>::Loose,
+        ordering: Ordering,
+    ) -> U::Packed {
+        let raw = R::fetch_add_(&self.raw, value, ordering);
+        // SAFETY: caller guarantees packed type invariants
+        unsafe { loose_to_packed(raw) }
+    }
+
+    /// Equivalent to [`core::sync::atomic::AtomicU64::fetch_sub`], but takes a loose
+    /// value and does not unpack.
+    ///
+    /// # Safety
+    ///
+    /// Caller must guarantee that the result of the operation is a valid bit pattern
+    /// for the packed type.
+    #[inline]
+    pub unsafe fn fetch_sub_loose(
+        &self,
+        value: <U::Packed as Unpack>::Loose,
+        ordering: Ordering,
+    ) -> U::Packed {
+        let raw = R::fetch_sub_(&self.raw, value, ordering);
+        // SAFETY: caller guarantees packed type invariants
+        unsafe { loose_to_packed(raw) }
+    }
+
+    /// Equivalent to [`core::sync::atomic::AtomicU64::fetch_update`].
+    #[inline]
+    pub fn fetch_update<F>(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        mut f: F,
+    ) -> Result<U, U>
+    where
+        F: FnMut(U) -> Option<U>,
+    {
+        self.fetch_update_packed(set_order, fetch_order, |old| {
+            f(old.unpack()).map(Pack::pack)
+        })
+        .map(Unpack::unpack)
+        .map_err(Unpack::unpack)
+    }
+
+    /// Equivalent to [`core::sync::atomic::AtomicU64::fetch_update`], but passes
+    /// packed values to `f` and does not unpack.
+    #[inline]
+    pub fn fetch_update_packed<F>(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        mut f: F,
+    ) -> Result<U::Packed, U::Packed>
+    where
+        F: FnMut(U::Packed) -> Option<U::Packed>,
+    {
+        let mut old = self.load_packed(fetch_order);
+        while let Some(new) = f(old) {
+            match self.compare_exchange_weak_packed(old, new, set_order, fetch_order) {
+                Ok(old) => return Ok(old),
+                Err(next) => old = next,
+            }
+        }
+        Err(old)
+    }
+
+    /// Equivalent to [`core::sync::atomic::AtomicU64::update`].
+    #[inline]
+    pub fn update<F>(&self, set_order: Ordering, fetch_order: Ordering, mut f: F) -> U
+    where
+        F: FnMut(U) -> U,
+    {
+        self.update_packed(set_order, fetch_order, |old| f(old.unpack()).pack())
+            .unpack()
+    }
+
+    /// Equivalent to [`core::sync::atomic::AtomicU64::update`], but passes
+    /// packed values to `f` and does not unpack.
+    #[inline]
+    pub fn update_packed<F>(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        mut f: F,
+    ) -> U::Packed
+    where
+        F: FnMut(U::Packed) -> U::Packed,
+    {
+        match self.fetch_update_packed(set_order, fetch_order, |old| Some(f(old))) {
+            Ok(old) | Err(old) => old,
+        }
+    }
 }
 
+impl<U, R> Atomic<U, R>
+where
+    U: Pack,
+    R: RawMut<<<U as Pack>::Packed as Unpack>::Loose>,
+{
+    /// Like [`core::sync::atomic::AtomicU64::get_mut`], but does not unpack.
+    #[inline]
+    pub fn get_mut_packed(&mut self) -> &mut U::Packed {
+        const {
+            assert!(
+                core::mem::size_of::<<crate::Packed<U> as Unpack>::Loose>()
+                    == core::mem::size_of::<U::Packed>()
+            );
+
+            assert!(
+                core::mem::align_of::<<crate::Packed<U> as Unpack>::Loose>()
+                    == core::mem::align_of::<U::Packed>()
+            );
+        }
+
+        // SAFETY: checked above that referenced types have same layout
+        unsafe {
+            core::mem::transmute::<&mut <<U as Pack>::Packed as Unpack>::Loose, &mut U::Packed>(
+                R::get_mut_(&mut self.raw),
+            )
+        }
+    }
+}
+
+macro_rules! impl_native {
+    ($loose:ty, $atomic:ty) => {
+        impl<U> Atomic<U, $atomic>
+        where
+            U: Pack,
+            U::Packed: Unpack<Loose = $loose>,
+        {
+            /// `const` version of [`Atomic::new_packed`] for built-in atomic types.
+            ///
+            /// [`Atomic::new_packed`] is not `const` for generic `R`, since it constructs
+            /// the underlying atomic via [`Raw::new_`].
+            #[inline]
+            pub const fn new_packed_const(packed: U::Packed) -> Self {
+                Self::from_raw(<$atomic>::new(packed_to_loose(packed)))
+            }
+
+            /// Equivalent to [`core::sync::atomic::AtomicU64::from_ptr`].
+            ///
+            /// # Safety
+            ///
+            /// Caller must uphold the requirements of [`core::sync::atomic::AtomicU64::from_ptr`],
+            /// and guarantee that `ptr` points to a valid bit pattern for the packed type
+            /// for the duration of lifetime `'a`.
+            #[inline]
+            pub unsafe fn from_ptr<'a>(ptr: *mut $loose) -> &'a Self {
+                // SAFETY: caller guarantees requirements of `from_ptr`, and
+                // `Atomic` is `repr(transparent)` over the underlying atomic
+                unsafe { &*(<$atomic>::from_ptr(ptr) as *const $atomic).cast::<Self>() }
+            }
+
+            /// Equivalent to [`core::sync::atomic::AtomicU64::as_ptr`].
+            #[inline]
+            pub const fn as_ptr(&self) -> *mut $loose {
+                self.raw.as_ptr()
+            }
+
+            /// Like [`core::sync::atomic::AtomicU64::from_mut`], but fails to compile
+            /// if the alignment of the native integer type differs from the atomic type.
+            ///
+            /// # Safety
+            ///
+            /// Caller must guarantee that `loose` is a valid bit pattern for the packed type.
+            #[inline]
+            pub unsafe fn from_mut(loose: &mut $loose) -> &mut Self {
+                const {
+                    assert!(core::mem::align_of::<$loose>() == core::mem::align_of::<$atomic>());
+                }
+
+                // SAFETY: checked above that referenced types have same layout,
+                // and caller guarantees bit pattern is valid
+                unsafe { &mut *(loose as *mut $loose).cast::<Self>() }
+            }
+
+            /// Block the current thread while the value is `expected`.
+            ///
+            /// Backed by futex for 32-bit packed types on Linux, and by a global
+            /// parking table otherwise. May return spuriously, so callers should
+            /// re-check the value (or use [`Atomic::wait_until`]).
+            #[cfg(feature = "std")]
+            #[inline]
+            pub fn wait(&self, expected: U::Packed) {
+                // SAFETY: `as_ptr` returns a valid and aligned pointer
+                unsafe {
+                    wait::wait(self.as_ptr(), packed_to_loose(expected), || {
+                        self.raw.load(Ordering::Acquire)
+                    })
+                }
+            }
+
+            /// Block the current thread until `f` returns `true`, and return
+            /// the value it accepted.
+            #[cfg(feature = "std")]
+            #[inline]
+            pub fn wait_until<F>(&self, mut f: F) -> U
+            where
+                F: FnMut(U) -> bool,
+            {
+                loop {
+                    let packed = self.load_packed(Ordering::Acquire);
+                    let unpacked = packed.unpack();
+                    if f(unpacked) {
+                        return unpacked;
+                    }
+                    self.wait(packed);
+                }
+            }
+
+            /// Wake up one thread blocked in [`Atomic::wait`] or [`Atomic::wait_until`].
+            #[cfg(feature = "std")]
+            #[inline]
+            pub fn notify_one(&self) {
+                // SAFETY: `as_ptr` returns a valid and aligned pointer
+                unsafe { wait::notify(self.as_ptr(), false) }
+            }
+
+            /// Wake up all threads blocked in [`Atomic::wait`] or [`Atomic::wait_until`].
+            #[cfg(feature = "std")]
+            #[inline]
+            pub fn notify_all(&self) {
+                // SAFETY: `as_ptr` returns a valid and aligned pointer
+                unsafe { wait::notify(self.as_ptr(), true) }
+            }
+        }
+    };
+}
+
+impl_native!(u8, core::sync::atomic::AtomicU8);
+impl_native!(u16, core::sync::atomic::AtomicU16);
+impl_native!(u32, core::sync::atomic::AtomicU32);
+impl_native!(u64, core::sync::atomic::AtomicU64);
+#[cfg(feature = "atomic-u128")]
+impl_native!(u128, portable_atomic::AtomicU128);
+
+#[cfg(feature = "critical-section")]
+impl_native!(u8, critical_section::AtomicU8);
+#[cfg(feature = "critical-section")]
+impl_native!(u16, critical_section::AtomicU16);
+#[cfg(feature = "critical-section")]
+impl_native!(u32, critical_section::AtomicU32);
+#[cfg(feature = "critical-section")]
+impl_native!(u64, critical_section::AtomicU64);
+#[cfg(all(feature = "critical-section", feature = "u128"))]
+impl_native!(u128, critical_section::AtomicU128);
+
 impl<U, R> Clone for Atomic<U, R>
 where
     R: Clone,
@@ -273,6 +600,14 @@ impl<U, R> From<R> for Atomic<U, R> {
     }
 }
 
+/// Loose type with a default atomic integer type.
+///
+/// Implemented for native integer types, but not [`Words`](crate::Words),
+/// so packed types wider than 128 bits can't be used with [`Atomic`].
+pub trait HasAtomic: Loose {
+    type Atomic: Raw<Self>;
+}
+
 /// Interface for underlying atomic integer.
 pub trait Raw<T>: core::fmt::Debug + Default + Send + Sync {
     fn new_(value: T) -> Self;
@@ -281,7 +616,7 @@ pub trait Raw<T>: core::fmt::Debug + Default + Send + Sync {
 
     fn store_(&self, value: T, ordering: Ordering);
 
-    fn get_mut_(&mut self) -> &mut T;
+    fn with_mut_<O, F: FnOnce(&mut T) -> O>(&mut self, f: F) -> O;
 
     fn compare_exchange_(
         &self,
@@ -300,17 +635,51 @@ pub trait Raw<T>: core::fmt::Debug + Default + Send + Sync {
     ) -> Result<T, T>;
 
     fn swap_(&self, value: T, ordering: Ordering) -> T;
+
+    fn fetch_and_(&self, value: T, ordering: Ordering) -> T;
+
+    fn fetch_or_(&self, value: T, ordering: Ordering) -> T;
+
+    fn fetch_xor_(&self, value: T, ordering: Ordering) -> T;
+
+    fn fetch_add_(&self, value: T, ordering: Ordering) -> T;
+
+    fn fetch_sub_(&self, value: T, ordering: Ordering) -> T;
+}
+
+/// Interface for underlying atomic integer that can be borrowed mutably.
+///
+/// Not implemented by atomic types that only provide scoped mutable access
+/// (e.g., `loom`, which only has `with_mut`).
+pub trait RawMut<T>: Raw<T> {
+    fn get_mut_(&mut self) -> &mut T;
 }
 
-/// Convenience macro for implementing [`Raw`].
+/// Convenience macro for implementing [`Raw`] and [`RawMut`].
 ///
 /// Should be called like: `impl_raw!(u64, my_atomic::AtomicU64)`.
 /// Forwards by calling the corresponding methods (with no trailing underscore)
 /// on the second type, which allows for some basic duck typing
 /// (e.g., second type can be a wrapper implementing [`core::ops::Deref`]).
+///
+/// For atomic types without `get_mut`, call like `impl_raw!(u64, my_atomic::AtomicU64, with_mut)`
+/// to forward to `with_mut` instead and skip implementing [`RawMut`].
 #[macro_export]
 macro_rules! impl_raw {
     ($raw:ty, $atomic:ty) => {
+        $crate::impl_raw!(@raw $raw, $atomic, |atomic, f| f(atomic.get_mut()));
+
+        impl $crate::atomic::RawMut<$raw> for $atomic {
+            #[inline]
+            fn get_mut_(&mut self) -> &mut $raw {
+                self.get_mut()
+            }
+        }
+    };
+    ($raw:ty, $atomic:ty, with_mut) => {
+        $crate::impl_raw!(@raw $raw, $atomic, |atomic, f| atomic.with_mut(f));
+    };
+    (@raw $raw:ty, $atomic:ty, |$atomic_mut:ident, $f:ident| $with_mut:expr) => {
         impl $crate::atomic::Raw<$raw> for $atomic {
             #[inline]
             fn new_(value: $raw) -> Self {
@@ -328,8 +697,9 @@ macro_rules! impl_raw {
             }
 
             #[inline]
-            fn get_mut_(&mut self) -> &mut $raw {
-                self.get_mut()
+            fn with_mut_<O, F: FnOnce(&mut $raw) -> O>(&mut self, $f: F) -> O {
+                let $atomic_mut = self;
+                $with_mut
             }
 
             #[inline]
@@ -358,13 +728,47 @@ macro_rules! impl_raw {
             fn swap_(&self, value: $raw, ordering: ::core::sync::atomic::Ordering) -> $raw {
                 self.swap(value, ordering)
             }
+
+            #[inline]
+            fn fetch_and_(&self, value: $raw, ordering: ::core::sync::atomic::Ordering) -> $raw {
+                self.fetch_and(value, ordering)
+            }
+
+            #[inline]
+            fn fetch_or_(&self, value: $raw, ordering: ::core::sync::atomic::Ordering) -> $raw {
+                self.fetch_or(value, ordering)
+            }
+
+            #[inline]
+            fn fetch_xor_(&self, value: $raw, ordering: ::core::sync::atomic::Ordering) -> $raw {
+                self.fetch_xor(value, ordering)
+            }
+
+            #[inline]
+            fn fetch_add_(&self, value: $raw, ordering: ::core::sync::atomic::Ordering) -> $raw {
+                self.fetch_add(value, ordering)
+            }
+
+            #[inline]
+            fn fetch_sub_(&self, value: $raw, ordering: ::core::sync::atomic::Ordering) -> $raw {
+                self.fetch_sub(value, ordering)
+            }
         }
     };
 }
 
-impl_raw!(u8, AtomicU8);
-impl_raw!(u16, AtomicU16);
-impl_raw!(u32, AtomicU32);
-impl_raw!(u64, AtomicU64);
-#[cfg(feature = "u128")]
-impl_raw!(u128, AtomicU128);
+impl_raw!(u8, core::sync::atomic::AtomicU8);
+impl_raw!(u16, core::sync::atomic::AtomicU16);
+impl_raw!(u32, core::sync::atomic::AtomicU32);
+impl_raw!(u64, core::sync::atomic::AtomicU64);
+#[cfg(feature = "atomic-u128")]
+impl_raw!(u128, portable_atomic::AtomicU128);
+
+#[cfg(feature = "loom")]
+impl_raw!(u8, loom::sync::atomic::AtomicU8, with_mut);
+#[cfg(feature = "loom")]
+impl_raw!(u16, loom::sync::atomic::AtomicU16, with_mut);
+#[cfg(feature = "loom")]
+impl_raw!(u32, loom::sync::atomic::AtomicU32, with_mut);
+#[cfg(feature = "loom")]
+impl_raw!(u64, loom::sync::atomic::AtomicU64, with_mut);
@@ -0,0 +1,141 @@
+//! Atomic integer types implemented with the [`critical-section`](https://github.com/rust-embedded/critical-section)
+//! crate, for targets without native compare-and-swap.
+//!
+//! These are not the default backend for [`Atomic`](crate::Atomic), and must be selected
+//! explicitly, e.g. `Atomic<U, critical_section::AtomicU32>`.
+
+use core::cell::UnsafeCell;
+use core::fmt::Debug;
+use core::sync::atomic::Ordering;
+
+macro_rules! atomic {
+    ($atomic:ident, $raw:ty) => {
+        #[doc = concat!("Atomic `", stringify!($raw), "` that runs every operation inside a critical section.")]
+        #[derive(Default)]
+        #[repr(transparent)]
+        pub struct $atomic(UnsafeCell<$raw>);
+
+        // SAFETY: all shared access goes through a critical section
+        unsafe impl Sync for $atomic {}
+
+        impl $atomic {
+            #[inline]
+            pub const fn new(value: $raw) -> Self {
+                Self(UnsafeCell::new(value))
+            }
+
+            /// # Safety
+            ///
+            /// Caller must guarantee that `ptr` is aligned and valid for reads and writes
+            /// for the duration of lifetime `'a`, and that every other access to that memory
+            /// during `'a` happens inside a critical section. Operations on this type are
+            /// plain reads and writes inside a critical section, so any non-atomic access
+            /// outside a critical section is a data race, and so is any native atomic access.
+            #[inline]
+            pub const unsafe fn from_ptr<'a>(ptr: *mut $raw) -> &'a Self {
+                // SAFETY: `Self` is `repr(transparent)` over `UnsafeCell<$raw>`,
+                // which has the same layout as `$raw`
+                unsafe { &*ptr.cast::<Self>() }
+            }
+
+            #[inline]
+            pub const fn as_ptr(&self) -> *mut $raw {
+                self.0.get()
+            }
+
+            #[inline]
+            pub fn get_mut(&mut self) -> &mut $raw {
+                self.0.get_mut()
+            }
+
+            #[inline]
+            pub fn load(&self, _: Ordering) -> $raw {
+                self.with(|value| *value)
+            }
+
+            #[inline]
+            pub fn store(&self, value: $raw, _: Ordering) {
+                self.with(|old| *old = value)
+            }
+
+            #[inline]
+            pub fn compare_exchange(
+                &self,
+                old: $raw,
+                new: $raw,
+                _: Ordering,
+                _: Ordering,
+            ) -> Result<$raw, $raw> {
+                self.with(|value| match *value == old {
+                    true => Ok(core::mem::replace(value, new)),
+                    false => Err(*value),
+                })
+            }
+
+            #[inline]
+            pub fn compare_exchange_weak(
+                &self,
+                old: $raw,
+                new: $raw,
+                success: Ordering,
+                failure: Ordering,
+            ) -> Result<$raw, $raw> {
+                self.compare_exchange(old, new, success, failure)
+            }
+
+            #[inline]
+            pub fn swap(&self, value: $raw, _: Ordering) -> $raw {
+                self.with(|old| core::mem::replace(old, value))
+            }
+
+            #[inline]
+            pub fn fetch_and(&self, value: $raw, _: Ordering) -> $raw {
+                self.with(|old| core::mem::replace(old, *old & value))
+            }
+
+            #[inline]
+            pub fn fetch_or(&self, value: $raw, _: Ordering) -> $raw {
+                self.with(|old| core::mem::replace(old, *old | value))
+            }
+
+            #[inline]
+            pub fn fetch_xor(&self, value: $raw, _: Ordering) -> $raw {
+                self.with(|old| core::mem::replace(old, *old ^ value))
+            }
+
+            #[inline]
+            pub fn fetch_add(&self, value: $raw, _: Ordering) -> $raw {
+                self.with(|old| core::mem::replace(old, old.wrapping_add(value)))
+            }
+
+            #[inline]
+            pub fn fetch_sub(&self, value: $raw, _: Ordering) -> $raw {
+                self.with(|old| core::mem::replace(old, old.wrapping_sub(value)))
+            }
+
+            #[inline]
+            fn with<O, F: FnOnce(&mut $raw) -> O>(&self, f: F) -> O {
+                ::critical_section::with(|_| {
+                    // SAFETY: critical section guarantees exclusive access
+                    f(unsafe { &mut *self.0.get() })
+                })
+            }
+        }
+
+        impl Debug for $atomic {
+            #[inline]
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                self.load(Ordering::Relaxed).fmt(f)
+            }
+        }
+
+        crate::impl_raw!($raw, $atomic);
+    };
+}
+
+atomic!(AtomicU8, u8);
+atomic!(AtomicU16, u16);
+atomic!(AtomicU32, u32);
+atomic!(AtomicU64, u64);
+#[cfg(feature = "u128")]
+atomic!(AtomicU128, u128);
@@ -0,0 +1,114 @@
+//! Blocking wait and notify on atomic integers, backed by futex for 32-bit
+//! integers on Linux and a global parking table otherwise.
+
+use std::sync::Condvar;
+use std::sync::Mutex;
+
+/// Block the current thread while `load()` returns `expected`.
+///
+/// May return spuriously.
+///
+/// # Safety
+///
+/// Caller must guarantee that `ptr` is aligned and valid for reads.
+#[inline]
+pub(crate) unsafe fn wait<T: Copy + PartialEq>(ptr: *mut T, expected: T, load: impl FnOnce() -> T) {
+    #[cfg(target_os = "linux")]
+    if core::mem::size_of::<T>() == 4 {
+        // SAFETY: `T` is `u32`, and caller guarantees `ptr` is valid
+        return unsafe { futex::wait(ptr.cast(), core::mem::transmute_copy(&expected)) };
+    }
+
+    park::wait(ptr as usize, || load() == expected)
+}
+
+/// Wake up one (if `all` is false) or all threads blocked in [`wait`] on `ptr`.
+///
+/// # Safety
+///
+/// Caller must guarantee that `ptr` is aligned and valid for reads.
+#[inline]
+pub(crate) unsafe fn notify<T>(ptr: *mut T, all: bool) {
+    #[cfg(target_os = "linux")]
+    if core::mem::size_of::<T>() == 4 {
+        // SAFETY: `T` is `u32`, and caller guarantees `ptr` is valid
+        return unsafe { futex::wake(ptr.cast(), if all { i32::MAX } else { 1 }) };
+    }
+
+    // Buckets are shared between addresses, so waking a single
+    // thread might wake one that is waiting on a different address.
+    let _ = all;
+    park::notify(ptr as usize)
+}
+
+#[cfg(target_os = "linux")]
+mod futex {
+    // Not using `FUTEX_PRIVATE_FLAG`, so that waiters in other processes
+    // sharing the same memory (e.g., via `Atomic::from_ptr`) can be woken.
+
+    pub(super) unsafe fn wait(ptr: *const u32, expected: u32) {
+        // SAFETY: caller guarantees `ptr` is valid
+        unsafe {
+            libc::syscall(
+                libc::SYS_futex,
+                ptr,
+                libc::FUTEX_WAIT,
+                expected,
+                core::ptr::null::<libc::timespec>(),
+            )
+        };
+    }
+
+    pub(super) unsafe fn wake(ptr: *const u32, count: i32) {
+        // SAFETY: caller guarantees `ptr` is valid
+        unsafe { libc::syscall(libc::SYS_futex, ptr, libc::FUTEX_WAKE, count) };
+    }
+}
+
+mod park {
+    use super::Condvar;
+    use super::Mutex;
+
+    struct Bucket {
+        mutex: Mutex<()>,
+        condvar: Condvar,
+    }
+
+    static BUCKETS: [Bucket; 64] = [const {
+        Bucket {
+            mutex: Mutex::new(()),
+            condvar: Condvar::new(),
+        }
+    }; 64];
+
+    fn bucket(address: usize) -> &'static Bucket {
+        // Fibonacci hashing
+        let hash = (address as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15) >> 58;
+        &BUCKETS[hash as usize]
+    }
+
+    pub(super) fn wait(address: usize, should_park: impl FnOnce() -> bool) {
+        let bucket = bucket(address);
+        let guard = bucket
+            .mutex
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+
+        // Checking under the bucket lock prevents lost wakeups, since
+        // notifiers acquire the same lock after updating the value.
+        if should_park() {
+            drop(bucket.condvar.wait(guard));
+        }
+    }
+
+    pub(super) fn notify(address: usize) {
+        let bucket = bucket(address);
+        drop(
+            bucket
+                .mutex
+                .lock()
+                .unwrap_or_else(|error| error.into_inner()),
+        );
+        bucket.condvar.notify_all();
+    }
+}
@@ -0,0 +1,124 @@
+use core::mem::MaybeUninit;
+
+use crate::words::View;
+use crate::InvalidBits;
+use crate::Loose;
+use crate::Unpack;
+
+/// Convert `packed` to its `N` least significant bytes in little-endian order.
+pub const fn to_le_bytes<T: Unpack, const N: usize>(packed: T) -> [u8; N] {
+    let loose = crate::convert::packed_to_loose(packed);
+    let view = View::new(&loose);
+
+    let mut bytes = [0; N];
+    let mut index = 0;
+    while index < N {
+        bytes[index] = view.extract(index * 8, 8) as u8;
+        index += 1;
+    }
+    bytes
+}
+
+/// Convert `packed` to its `N` least significant bytes in big-endian order.
+pub const fn to_be_bytes<T: Unpack, const N: usize>(packed: T) -> [u8; N] {
+    let mut bytes = to_le_bytes::<T, N>(packed);
+    reverse(&mut bytes);
+    bytes
+}
+
+/// Convert `packed` to its `N` least significant bytes in native order.
+pub const fn to_ne_bytes<T: Unpack, const N: usize>(packed: T) -> [u8; N] {
+    match cfg!(target_endian = "little") {
+        true => to_le_bytes(packed),
+        false => to_be_bytes(packed),
+    }
+}
+
+/// Convert from `N` bytes in little-endian order to `T`, if they are a valid bit pattern.
+pub const fn try_from_le_bytes<T: Unpack, const N: usize>(
+    bytes: [u8; N],
+) -> Result<T, InvalidBits> {
+    const { assert!(N <= core::mem::size_of::<T::Loose>()) }
+
+    crate::invalid::try_loose_to_packed(le_bytes_to_loose(bytes))
+}
+
+/// Convert from `N` bytes in big-endian order to `T`, if they are a valid bit pattern.
+pub const fn try_from_be_bytes<T: Unpack, const N: usize>(
+    mut bytes: [u8; N],
+) -> Result<T, InvalidBits> {
+    reverse(&mut bytes);
+    try_from_le_bytes(bytes)
+}
+
+/// Convert from `N` bytes in native order to `T`, if they are a valid bit pattern.
+pub const fn try_from_ne_bytes<T: Unpack, const N: usize>(
+    bytes: [u8; N],
+) -> Result<T, InvalidBits> {
+    match cfg!(target_endian = "little") {
+        true => try_from_le_bytes(bytes),
+        false => try_from_be_bytes(bytes),
+    }
+}
+
+const fn reverse<const N: usize>(bytes: &mut [u8; N]) {
+    let mut index = 0;
+    while index < N / 2 {
+        let byte = bytes[index];
+        bytes[index] = bytes[N - 1 - index];
+        bytes[N - 1 - index] = byte;
+        index += 1;
+    }
+}
+
+/// Zero-extend `N` bytes in little-endian order to a loose type.
+const fn le_bytes_to_loose<L: Loose, const N: usize>(bytes: [u8; N]) -> L {
+    if !L::WORDS {
+        let mut value = [0; 16];
+        let mut index = 0;
+        while index < N {
+            value[index] = bytes[index];
+            index += 1;
+        }
+
+        return u128_to_loose(u128::from_le_bytes(value));
+    }
+
+    let mut loose = MaybeUninit::<L>::zeroed();
+    let words = loose.as_mut_ptr().cast::<u64>();
+
+    let mut index = 0;
+    while index < N {
+        // SAFETY: `Words` is `repr(transparent)` over an array of `u64`,
+        // and the caller checked that `N` fits in its size
+        unsafe { *words.add(index / 8) |= (bytes[index] as u64) << (index % 8 * 8) };
+        index += 1;
+    }
+
+    // SAFETY: all words are initialized by `zeroed`
+    unsafe { loose.assume_init() }
+}
+
+/// Truncate a `u128` to a native integer type.
+const fn u128_to_loose<L: Loose>(value: u128) -> L {
+    union Narrow<L: Copy> {
+        loose: L,
+        u8: u8,
+        u16: u16,
+        u32: u32,
+        u64: u64,
+        u128: u128,
+    }
+
+    // SAFETY: only called for native integer types (not `Words`),
+    // so the field with the same size initializes `loose`.
+    unsafe {
+        match core::mem::size_of::<L>() {
+            1 => Narrow { u8: value as u8 }.loose,
+            2 => Narrow { u16: value as u16 }.loose,
+            4 => Narrow { u32: value as u32 }.loose,
+            8 => Narrow { u64: value as u64 }.loose,
+            _ => Narrow { u128: value }.loose,
+        }
+    }
+}
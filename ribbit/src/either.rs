@@ -0,0 +1,259 @@
+use core::fmt::Debug;
+use core::hash::Hash;
+use core::marker::PhantomData;
+
+use crate::convert;
+use crate::private::Rule;
+use crate::private::Variant;
+use crate::Loose;
+use crate::Max;
+use crate::Pack;
+use crate::Unpack;
+
+/// Value of one of two types, without a success or failure connotation like [`Result`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+/// Packed representation of [`Either`].
+///
+/// Stores a 1-bit tag (set for [`Either::Right`]) in the least significant bit,
+/// followed by the payload, for `1 + max(L::BITS, R::BITS)` bits in total. The
+/// underlying native integer type is the larger of [`Unpack::Tagged`] of `L` and `R`,
+/// i.e. the smallest one that fits both payloads and the tag.
+#[repr(transparent)]
+pub struct EitherPacked<L: Unpack, R: Unpack>
+where
+    L::Tagged: Max<R::Tagged>,
+{
+    value: <L::Tagged as Max<R::Tagged>>::Output,
+    r#type: PhantomData<(L, R)>,
+}
+
+/// Packed representation of [`Result`], with the same layout as [`EitherPacked`]
+/// (i.e., the tag bit is set for [`Err`]).
+#[repr(transparent)]
+pub struct ResultPacked<T: Unpack, E: Unpack>
+where
+    T::Tagged: Max<E::Tagged>,
+{
+    value: <T::Tagged as Max<E::Tagged>>::Output,
+    r#type: PhantomData<(T, E)>,
+}
+
+/// Size of a 1-bit tag plus the larger of `l` and `r`.
+const fn bits(l: usize, r: usize) -> usize {
+    1 + if l > r { l } else { r }
+}
+
+const fn assert_fits<P: Unpack>() {
+    assert!(
+        P::BITS <= core::mem::size_of::<P::Loose>() * 8,
+        "Tag and payload do not fit in the largest native integer type",
+    );
+}
+
+/// Pack `value` above a tag bit that is set if `right`.
+#[inline]
+fn pack<P: Unpack, W: Loose>(value: P, right: bool) -> W {
+    let value = convert::loose_to_loose::<P::Loose, W>(convert::packed_to_loose(value)) << 1;
+    match right {
+        false => value,
+        true => value | W::ONE,
+    }
+}
+
+/// Unpack the value above the tag bit.
+///
+/// # Safety
+///
+/// Caller must guarantee that `value` was packed from a `P`.
+#[inline]
+unsafe fn unpack<P: Unpack, W: Loose>(value: W) -> P {
+    // SAFETY: caller guarantees bit pattern is valid
+    unsafe { convert::loose_to_packed(convert::loose_to_loose::<W, P::Loose>(value >> 1)) }
+}
+
+/// Payload of type `P` above the tag bit, padded to `bits`.
+const fn payload<P: Unpack>(bits: usize) -> Rule {
+    Rule::Field {
+        name: "",
+        offset: 1,
+        size: bits - 1,
+        bits: P::BITS,
+        rules: P::RULES,
+    }
+}
+
+macro_rules! impl_either {
+    (
+        $packed:ident<$l:ident, $r:ident>,
+        $unpacked:ident::{$left:ident, $right:ident},
+        $is_left:ident,
+        $is_right:ident,
+        $get_left:ident,
+        $get_right:ident $(,)?
+    ) => {
+        impl<$l: Unpack, $r: Unpack> $packed<$l, $r>
+        where
+            $l::Tagged: Max<$r::Tagged>,
+        {
+            #[inline]
+            pub fn $is_left(self) -> bool {
+                self.value & <Self as Unpack>::Loose::ONE == <Self as Unpack>::Loose::ZERO
+            }
+
+            #[inline]
+            pub fn $is_right(self) -> bool {
+                !self.$is_left()
+            }
+
+            #[inline]
+            pub fn $get_left(self) -> Option<$l> {
+                match self.$is_left() {
+                    // SAFETY: tag bit guarantees value was packed from `$l`
+                    true => Some(unsafe { unpack(self.value) }),
+                    false => None,
+                }
+            }
+
+            #[inline]
+            pub fn $get_right(self) -> Option<$r> {
+                match self.$is_right() {
+                    // SAFETY: tag bit guarantees value was packed from `$r`
+                    true => Some(unsafe { unpack(self.value) }),
+                    false => None,
+                }
+            }
+        }
+
+        impl<$l: Unpack, $r: Unpack> Clone for $packed<$l, $r>
+        where
+            $l::Tagged: Max<$r::Tagged>,
+        {
+            #[inline]
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<$l: Unpack, $r: Unpack> Copy for $packed<$l, $r> where $l::Tagged: Max<$r::Tagged> {}
+
+        impl<$l: Unpack, $r: Unpack> Debug for $packed<$l, $r>
+        where
+            $l::Tagged: Max<$r::Tagged>,
+            $l::Unpacked: Debug,
+            $r::Unpacked: Debug,
+        {
+            #[inline]
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                self.unpack().fmt(f)
+            }
+        }
+
+        impl<$l: Unpack, $r: Unpack> PartialEq for $packed<$l, $r>
+        where
+            $l::Tagged: Max<$r::Tagged>,
+        {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.value == other.value
+            }
+        }
+
+        impl<$l: Unpack, $r: Unpack> Eq for $packed<$l, $r> where $l::Tagged: Max<$r::Tagged> {}
+
+        impl<$l: Unpack, $r: Unpack> Hash for $packed<$l, $r>
+        where
+            $l::Tagged: Max<$r::Tagged>,
+        {
+            #[inline]
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                self.value.hash(state)
+            }
+        }
+
+        unsafe impl<$l: Pack, $r: Pack> Pack for $unpacked<$l, $r>
+        where
+            <$l::Packed as Unpack>::Tagged: Max<<$r::Packed as Unpack>::Tagged>,
+        {
+            type Packed = $packed<$l::Packed, $r::Packed>;
+
+            #[inline]
+            fn pack(self) -> Self::Packed {
+                const { assert_fits::<Self::Packed>() }
+                let value = match self {
+                    $unpacked::$left(left) => pack(left.pack(), false),
+                    $unpacked::$right(right) => pack(right.pack(), true),
+                };
+
+                $packed {
+                    value,
+                    r#type: PhantomData,
+                }
+            }
+        }
+
+        unsafe impl<$l: Unpack, $r: Unpack> Unpack for $packed<$l, $r>
+        where
+            $l::Tagged: Max<$r::Tagged>,
+        {
+            const BITS: usize = bits($l::BITS, $r::BITS);
+            type Unpacked = $unpacked<$l::Unpacked, $r::Unpacked>;
+            type Loose = <$l::Tagged as Max<$r::Tagged>>::Output;
+            // Fits `BITS + 1` only if the loose type has a spare bit, which is
+            // not known at the type level
+            type Tagged = <Self::Loose as Unpack>::Tagged;
+            type Raw = Self::Loose;
+            const NAME: &'static str = stringify!($unpacked);
+            const RULES: &'static [Rule] = &[Rule::Enum {
+                name: "",
+                offset: 0,
+                size: 1,
+                variants: &[
+                    Variant {
+                        discriminant: 0,
+                        mask: 1,
+                        rules: &[payload::<$l>(Self::BITS)],
+                    },
+                    Variant {
+                        discriminant: 1,
+                        mask: 1,
+                        rules: &[payload::<$r>(Self::BITS)],
+                    },
+                ],
+                unknown: false,
+            }];
+
+            #[inline]
+            fn unpack(self) -> Self::Unpacked {
+                const { assert_fits::<Self>() }
+                match self.$is_left() {
+                    // SAFETY: tag bit guarantees value was packed from `$l`
+                    true => $unpacked::$left(unsafe { unpack::<$l, _>(self.value) }.unpack()),
+                    // SAFETY: tag bit guarantees value was packed from `$r`
+                    false => $unpacked::$right(unsafe { unpack::<$r, _>(self.value) }.unpack()),
+                }
+            }
+
+            #[inline]
+            fn into_raw(self) -> Self::Raw {
+                self.value
+            }
+
+            #[inline]
+            unsafe fn from_raw_unchecked(raw: Self::Raw) -> Self {
+                crate::private::check::<Self>(raw);
+                Self {
+                    value: raw,
+                    r#type: PhantomData,
+                }
+            }
+        }
+    };
+}
+
+impl_either!(EitherPacked<L, R>, Either::{Left, Right}, is_left, is_right, left, right);
+impl_either!(ResultPacked<T, E>, Result::{Ok, Err}, is_ok, is_err, ok, err);
@@ -0,0 +1,383 @@
+use core::fmt::Display;
+
+use crate::words::View;
+use crate::Loose;
+use crate::Unpack;
+
+/// Error returned when raw bits are not a valid bit pattern for a packed type.
+///
+/// Reports the innermost field, discriminant, or type that failed validation,
+/// and the offset of the first invalid bit relative to the outermost type.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct InvalidBits {
+    kind: Invalid,
+    name: &'static str,
+    offset: usize,
+}
+
+/// Reason that raw bits are invalid.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Invalid {
+    /// Discriminant does not match any variant.
+    Discriminant,
+    /// Non-zero field is zero.
+    Zero,
+    /// Bits not occupied by any field are set.
+    Unused,
+}
+
+impl InvalidBits {
+    const fn new(kind: Invalid, name: &'static str, offset: usize) -> Self {
+        Self { kind, name, offset }
+    }
+
+    /// Attribute errors from types without named fields (e.g., [`NonZeroU8`](core::num::NonZeroU8))
+    /// to the enclosing field.
+    const fn or_name(self, name: &'static str) -> Self {
+        match self.name.is_empty() {
+            true => Self { name, ..self },
+            false => self,
+        }
+    }
+
+    #[inline]
+    pub const fn kind(&self) -> Invalid {
+        self.kind
+    }
+
+    /// Name of the field (e.g., `field` or `Variant.field`), enum, or variant.
+    #[inline]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Offset of the first invalid bit.
+    #[inline]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl Invalid {
+    const fn reason(self) -> &'static str {
+        match self {
+            Invalid::Discriminant => "unknown discriminant",
+            Invalid::Zero => "zero value for non-zero field",
+            Invalid::Unused => "unused bits set",
+        }
+    }
+}
+
+impl Display for InvalidBits {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "Invalid bits: {} in `{}` at offset {}",
+            self.kind.reason(),
+            self.name,
+            self.offset
+        )
+    }
+}
+
+impl core::error::Error for InvalidBits {}
+
+/// Validity of the bits of a packed type, relative to the start of the type.
+#[doc(hidden)]
+#[derive(Copy, Clone, Debug)]
+pub enum Rule {
+    /// `size` bits at `offset` must be zero.
+    Zero {
+        name: &'static str,
+        offset: usize,
+        size: usize,
+    },
+    /// `size` bits at `offset` must not all be zero.
+    NonZero {
+        name: &'static str,
+        offset: usize,
+        size: usize,
+    },
+    /// Field of `size` bits at `offset`, holding a packed type of `bits` bits
+    /// whose validity is described by `rules`.
+    Field {
+        name: &'static str,
+        offset: usize,
+        size: usize,
+        bits: usize,
+        rules: &'static [Rule],
+    },
+    /// Discriminant of `size` bits at `offset`, which must match one of `variants`
+    /// unless there is a catch-all variant.
+    Enum {
+        name: &'static str,
+        offset: usize,
+        size: usize,
+        variants: &'static [Variant],
+        unknown: bool,
+    },
+    /// Either exactly `value`, or valid according to `rules`.
+    Or { value: u128, rules: &'static [Rule] },
+}
+
+impl Rule {
+    /// All `size` bits of an unnamed type must not be zero.
+    pub const fn non_zero(size: usize) -> Self {
+        Self::NonZero {
+            name: "",
+            offset: 0,
+            size,
+        }
+    }
+}
+
+#[doc(hidden)]
+#[derive(Copy, Clone, Debug)]
+pub struct Variant {
+    pub discriminant: u128,
+    /// Mask of the discriminant bits used by this variant.
+    pub mask: u128,
+    pub rules: &'static [Rule],
+}
+
+/// Validate the `size` bits of `view` at `base` against `rules`.
+///
+/// Offsets in `rules` are relative to `base`, which is the offset of the
+/// enclosing field relative to the outermost type.
+const fn validate(view: View, base: usize, size: usize, rules: &[Rule]) -> Result<(), InvalidBits> {
+    let mut index = 0;
+
+    while index < rules.len() {
+        match rules[index] {
+            Rule::Zero { name, offset, size } => {
+                if let Some(offset) = view.first_set(base + offset, size) {
+                    return Err(InvalidBits::new(Invalid::Unused, name, offset));
+                }
+            }
+            Rule::NonZero { name, offset, size } => {
+                if view.first_set(base + offset, size).is_none() {
+                    return Err(InvalidBits::new(Invalid::Zero, name, base + offset));
+                }
+            }
+            Rule::Field {
+                name,
+                offset,
+                size,
+                bits,
+                rules,
+            } => {
+                if let Some(offset) = view.first_set(base + offset + bits, size - bits) {
+                    return Err(InvalidBits::new(Invalid::Unused, name, offset));
+                }
+
+                if let Err(error) = validate(view, base + offset, bits, rules) {
+                    return Err(error.or_name(name));
+                }
+            }
+            Rule::Enum {
+                name,
+                offset,
+                size: size_discriminant,
+                variants,
+                unknown,
+            } => {
+                let discriminant = view.extract(base + offset, size_discriminant);
+
+                let mut variant = 0;
+                while variant < variants.len()
+                    && discriminant & variants[variant].mask != variants[variant].discriminant
+                {
+                    variant += 1;
+                }
+
+                match variant < variants.len() {
+                    true => {
+                        if let Err(error) = validate(view, base, size, variants[variant].rules) {
+                            return Err(error);
+                        }
+                    }
+                    false if unknown => (),
+                    false => {
+                        return Err(InvalidBits::new(Invalid::Discriminant, name, base + offset))
+                    }
+                }
+            }
+            Rule::Or {
+                value: expected,
+                rules,
+            } => {
+                let equal = view.extract(base, size) == expected
+                    && (size <= 128 || view.first_set(base + 128, size - 128).is_none());
+
+                if !equal {
+                    if let Err(error) = validate(view, base, size, rules) {
+                        return Err(error);
+                    }
+                }
+            }
+        }
+
+        index += 1;
+    }
+
+    Ok(())
+}
+
+/// Convert from a native integer type to `T`, if `loose` is a valid bit pattern.
+pub const fn try_loose_to_packed<T: Unpack>(loose: T::Loose) -> Result<T, InvalidBits> {
+    match validate_loose::<T>(loose) {
+        // SAFETY: validated bit pattern
+        Ok(()) => Ok(unsafe { crate::convert::loose_to_packed(loose) }),
+        Err(error) => Err(error),
+    }
+}
+
+/// With the `checked` feature, panic if `loose` is not a valid bit pattern for `T`.
+///
+/// Called by unchecked conversions, which would otherwise cause undefined behavior.
+#[inline]
+pub const fn check<T: Unpack>(loose: T::Loose) {
+    if !cfg!(feature = "checked") {
+        return;
+    }
+
+    if let Err(error) = validate_loose::<T>(loose) {
+        let mut message = Message::new();
+        message.push("Invalid bits for `");
+        message.push(T::NAME);
+        message.push("` (raw value 0x");
+        message.push_hex(View::new(&loose));
+        message.push("): ");
+        message.push(error.kind.reason());
+        message.push(" in `");
+        message.push(error.name);
+        message.push("` at offset ");
+        message.push_decimal(error.offset as u128);
+        panic!("{}", message.as_str());
+    }
+}
+
+const fn validate_loose<T: Unpack>(loose: T::Loose) -> Result<(), InvalidBits> {
+    let view = View::new(&loose);
+
+    if let Some(offset) = view.first_set(T::BITS, view.len().saturating_sub(T::BITS)) {
+        return Err(InvalidBits::new(Invalid::Unused, T::NAME, offset));
+    }
+
+    match validate(view, 0, T::BITS, T::RULES) {
+        Ok(()) => Ok(()),
+        Err(error) => Err(error.or_name(T::NAME)),
+    }
+}
+
+/// Fixed-size buffer for formatting panic messages in `const` contexts.
+struct Message {
+    buffer: [u8; 256],
+    len: usize,
+}
+
+impl Message {
+    const fn new() -> Self {
+        Self {
+            buffer: [0; 256],
+            len: 0,
+        }
+    }
+
+    /// Append `string`, truncating if the buffer is full.
+    const fn push(&mut self, string: &str) {
+        let bytes = string.as_bytes();
+        let mut index = 0;
+        while index < bytes.len() && self.len < self.buffer.len() {
+            self.buffer[self.len] = bytes[index];
+            self.len += 1;
+            index += 1;
+        }
+    }
+
+    /// Append `value` in `radix`, padded with zeros to at least `width` digits.
+    const fn push_digits(&mut self, mut value: u128, radix: u128, width: usize) {
+        let mut digits = [0u8; 128];
+        let mut len = 0;
+        loop {
+            digits[len] = b"0123456789abcdef"[(value % radix) as usize];
+            len += 1;
+            value /= radix;
+            if value == 0 && len >= width {
+                break;
+            }
+        }
+
+        while len > 0 && self.len < self.buffer.len() {
+            len -= 1;
+            self.buffer[self.len] = digits[len];
+            self.len += 1;
+        }
+    }
+
+    const fn push_hex(&mut self, view: View) {
+        let words = match view {
+            View::Native(value) => return self.push_digits(value, 16, 1),
+            View::Words(words) => words,
+        };
+
+        // Skip leading zero words, and pad the rest to 16 digits
+        let mut index = words.len();
+        while index > 1 && words[index - 1] == 0 {
+            index -= 1;
+        }
+
+        let mut width = 1;
+        while index > 0 {
+            index -= 1;
+            self.push_digits(words[index] as u128, 16, width);
+            width = 16;
+        }
+    }
+
+    const fn push_decimal(&mut self, value: u128) {
+        self.push_digits(value, 10, 1)
+    }
+
+    const fn as_str(&self) -> &str {
+        let (bytes, _) = self.buffer.split_at(self.len);
+        match core::str::from_utf8(bytes) {
+            Ok(string) => string,
+            // Truncated in the middle of a character
+            Err(error) => match core::str::from_utf8(bytes.split_at(error.valid_up_to()).0) {
+                Ok(string) => string,
+                Err(_) => "",
+            },
+        }
+    }
+}
+
+/// Zero-extend a native integer type to `u128`.
+///
+/// See [`View::new`] for a conversion that also supports [`crate::Words`].
+pub(crate) const fn loose_to_u128<L: Loose>(loose: L) -> u128 {
+    union Widen<L: Copy> {
+        loose: L,
+        u8: u8,
+        u16: u16,
+        u32: u32,
+        u64: u64,
+        u128: u128,
+    }
+
+    assert!(!L::WORDS);
+    let widen = Widen { loose };
+
+    // SAFETY: checked above that `L` is a native integer type,
+    // so the field with the same size is initialized.
+    unsafe {
+        match core::mem::size_of::<L>() {
+            1 => widen.u8 as u128,
+            2 => widen.u16 as u128,
+            4 => widen.u32 as u128,
+            8 => widen.u64 as u128,
+            _ => widen.u128,
+        }
+    }
+}
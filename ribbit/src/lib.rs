@@ -6,10 +6,9 @@
 //!
 //! The driving motivation for this crate is lock-free programming,
 //! which often requires packing data into a `u64` or `u128` so it
-//! can be atomically updated. As a result, we don't support
-//! parsing-related functionality, like checked construction of a packed
-//! type from arbitrary bytes, packed types larger than 128 bits,
-//! or non-native endianness. We also don't suport arrays or
+//! can be atomically updated. As a result, enums and atomic packed
+//! types are limited to 128 bits, and only structs can be packed into
+//! wider arrays of words ([`Words`], up to 512 bits). We also don't suport
 //! anonymous tuples.
 //!
 //! This crate does provide the following features that were hard to
@@ -21,6 +20,7 @@
 //! - `const` inherent methods and constructors (that work with generics, on stable Rust)
 //! - Append-only: does not overwrite original type
 //! - Nonzero support (e.g., can use [`NonZeroU64`] to enable niche optimizations)
+//! - Tagged pointers (e.g., [`Ptr`] alongside a generation counter to prevent ABA)
 //!
 //! See also:
 //! - [bitfield-struct-rs](https://github.com/wrenger/bitfield-struct-rs)
@@ -34,6 +34,17 @@
 //! - The `u128` feature enables support for packed types up to 128 bits instead of 64 bits.
 //! - The `atomic-u128` feature enables support for atomic operations on 128 bit packed types via
 //!   the [`portable-atomic`](https://github.com/taiki-e/portable-atomic) crate.
+//! - The `critical-section` feature adds atomic integer types in `ribbit::atomic::critical_section`,
+//!   implemented with the [`critical-section`](https://github.com/rust-embedded/critical-section)
+//!   crate, for targets without native compare-and-swap. They are not the default, and must be
+//!   selected explicitly, e.g. `Atomic<U, critical_section::AtomicU32>`.
+//! - The `std` feature enables blocking `wait` and `notify` operations on atomic packed types,
+//!   backed by futex on Linux.
+//! - The `loom` feature backs atomic operations with the [`loom`](https://github.com/tokio-rs/loom)
+//!   crate when compiled with `--cfg loom`, for model checking concurrent code.
+//! - The `checked` feature validates the bit pattern on every unchecked conversion (e.g.,
+//!   `from_raw_unchecked` and [`convert::loose_to_packed`]) and every generated `unpack`,
+//!   panicking with the type name and raw value instead of causing undefined behavior.
 //!
 //! # Examples
 //!
@@ -97,6 +108,20 @@
 //!     );
 //! };
 //!
+//! // Spare discriminant support
+//! #[derive(ribbit::Pack, Copy, Clone)]
+//! #[ribbit(size = 2)]
+//! enum Spare {
+//!     A,
+//!     B,
+//!     C,
+//! }
+//!
+//! const _: () = assert!(
+//!     core::mem::size_of::<ribbit::Packed<Spare>>() ==
+//!     core::mem::size_of::<ribbit::Packed<ribbit::Niche<Spare>>>(),
+//! );
+//!
 //! # }
 //! # // HACK: needed to resolve module paths correctly
 //! # // https://users.rust-lang.org/t/private-fields-in-macro-generated-structs/120052
@@ -119,6 +144,8 @@
 //! assert_ne!(tuple.unpack(), Enum::Unit);
 //!
 //! // Atomic support
+//! # #[cfg(all(feature = "atomic", not(loom)))]
+//! # {
 //! use core::sync::atomic::Ordering;
 //! let atomic = ribbit::Atomic::<Enum>::new_packed(tuple);
 //! // Operate on packed types
@@ -137,8 +164,12 @@
 //!     unit.unpack(),
 //! );
 //! # }
+//! # }
 //! ```
 
+#[cfg(feature = "std")]
+extern crate std;
+
 /// Derive a packed representation of the (unpacked) input type.
 ///
 /// The packed type is backed by a single raw type ([`Unpack::Raw`])
@@ -147,13 +178,23 @@
 /// **Trait implementations**.
 ///
 /// Some traits are implemented unconditionally---[`Pack`](trait@Pack) for the unpacked type,
-/// and [`Unpack`][trait@Unpack], [`Copy`], and [`Clone`] for the packed type---while
+/// and [`Unpack`][trait@Unpack], [`Copy`], [`Clone`], and [`TryFrom`] the loose type
+/// (see `try_from_raw` below) for the packed type---while
 /// other traits can optionally be derived for the packed type:
 /// - [`From`]: generates bidirectional implementations between packed and unpacked types
 /// - [`Debug`][core::fmt::Debug]: forwards to [`Debug`][core::fmt::Debug] on the unpacked type
 /// - [`Hash`][core::hash::Hash], [`Ord`], and [`Eq`]: same as the standard derives,
 ///   but with no bounds on generic parameters
 ///   ([perfect derives](https://smallcultfollowing.com/babysteps//blog/2022/04/12/implied-bounds-and-perfect-derive/)).
+/// - `Atomic` (structs only): generates an extension trait `{Unpacked}AtomicExt` for `ribbit::Atomic` (requires
+///   the `atomic` feature) with per-field methods `load_n`, `store_n`, `swap_n`,
+///   and `fetch_update_n`, which read or update a single field and leave the rest unchanged.
+///   Single-bit fields also get `set_n`, `clear_n`, and `toggle_n`, which use native atomic
+///   bitwise operations instead of a compare-and-swap loop. Integer fields also get wrapping
+///   `fetch_add_n` and `fetch_sub_n`, which use native atomic arithmetic when the field
+///   occupies the most significant bits of [`Unpack::Loose`], or sits directly below a
+///   `guard` field. Otherwise they fall back to a compare-and-swap loop, because a carry
+///   into other fields or unoccupied bits (which must be zero) would be invalid.
 ///
 /// **Method implementations**.
 ///
@@ -161,8 +202,30 @@
 /// - `new`: Safe constructors (enum variants with name `v` have function `new_v`)
 /// - `get`: Field getters (fields with name `n` have method `n`; fields with index `i` have method `_i`)
 /// - `with`: Field updaters (immutable) (fields with name `n` have method `with_n`; fields with index `i` have method `with_i`)
+/// - `get` and `with` for arrays: Element getters and updaters taking an index, which panic if the index
+///   is out of bounds, while the whole array is accessed by `n_array` and `with_n_array`
+/// - `get` and `with` for enums: Variant field getters and updaters, which return [`None`] if the
+///   packed value holds a different variant (fields of variant `v` have methods `v_n` and `with_v_n`).
+///   The `non_exhaustive` variant has no updaters, since they could write a known discriminant.
 /// - `into_raw`: Conversion to raw type
 /// - `from_raw_unchecked`: Unsafe constructors from raw type (enum variants with name `v` have function `v_from_raw_unchecked`)
+/// - `try_from_raw`: Checked constructor from raw type, which returns an [`InvalidBits`] error
+///   reporting the field or discriminant at fault if the raw value has an unknown enum discriminant
+///   (including in nested types), a zero non-zero field, or non-zero unused bits
+/// - `to_le_bytes`, `to_be_bytes`, and `to_ne_bytes`: Conversion to `ceil(BITS / 8)` bytes (e.g., 3 bytes
+///   for a 24-bit type) in little-endian, big-endian, or native byte order
+/// - `try_from_le_bytes`, `try_from_be_bytes`, and `try_from_ne_bytes`: Checked constructors from bytes,
+///   with the same validation as `try_from_raw` (and skipped along with it)
+/// - Enum variants: discriminant (`discriminant`), predicates (variants with name `v` have method `is_v`),
+///   and accessors (variants with name `v` have method `v` returning an [`Option`] of the payload
+///   view type `{Packed}{V}`, which has `into_raw` and field getters), as well as associated
+///   constants `VARIANT_COUNT` and `DISCRIMINANTS` (in declaration order, excluding any `non_exhaustive` variant).
+///   Names that collide with other generated methods (e.g., variant `IntoRaw` and `into_raw`) are
+///   reported at compile time, and can be changed with the attributes below.
+/// - `view` for enums: Conversion to an enum `{Unpacked}View` whose variants hold payload views instead
+///   of unpacked fields, for matching on a packed enum without unpacking nested types. The view enum
+///   and payload views implement [`Debug`][core::fmt::Debug] (printing the raw payload), [`PartialEq`],
+///   and [`Eq`]
 ///
 /// <div class = "warning">
 ///
@@ -227,6 +290,13 @@
 ///     packed(vis = "pub(self)", rename = "EnumPacked"),
 ///     // Control generation of packed to raw conversion method.
 ///     into_raw(vis = "pub(self)", rename = "into_raw", skip = false),
+///     // Control generation of checked constructor function and `TryFrom` implementation.
+///     try_from_raw(vis = "pub(self)", rename = "try_from_raw", skip = false),
+///     // Control generation of byte conversion methods, where `rename` replaces the
+///     // `bytes` suffix (e.g., `to_le_bytes`).
+///     bytes(vis = "pub(self)", rename = "bytes", skip = false),
+///     // Control generation of `view` method and `{Unpacked}View` enum (enums only).
+///     view(vis = "pub(self)", rename = "view", skip = false),
 ///     // Custom derives for the packed type.
 ///     derive(Debug, From, Hash, Ord, Eq),
 ///     // Forward arbitrary tokens as attributes on the packed type.
@@ -236,6 +306,48 @@
 /// # }
 /// ```
 ///
+/// **Enum attributes**.
+///
+/// These can be used on top-level enums (and `non_exhaustive` on a single variant),
+/// in addition to all item attributes above.
+///
+/// ```rust
+/// # mod enum_attributes {
+/// #[derive(ribbit::Pack, Copy, Clone)]
+/// #[ribbit(
+///     size = 16,
+///     // Size and offset of the discriminant in bits. By default, the discriminant
+///     // is the minimum size required and sits in the least significant bits.
+///     //
+///     // Variant payloads sit above a discriminant at offset 0, and below it otherwise.
+///     discriminant(size = 4, offset = 12),
+///     //
+///     // Alternatively, `discriminant = "prefix"` assigns variable-length, prefix-free
+///     // discriminants, so variants with larger payloads get shorter discriminants.
+///     //
+///     // Control generation of the discriminant method and associated constants.
+///     variants(
+///         discriminant(vis = "pub(self)", rename = "discriminant", skip = false),
+///         variant_count(vis = "pub(self)", rename = "VARIANT_COUNT", skip = false),
+///         discriminants(vis = "pub(self)", rename = "DISCRIMINANTS", skip = false),
+///     ),
+/// )]
+/// enum Enum {
+///     // Control generation of the variant predicate and payload accessor.
+///     #[ribbit(size = 12, is(rename = "is_short"), get(rename = "short", skip = false))]
+///     Short(ribbit::u12),
+///     #[ribbit(size = 8)]
+///     Byte(u8),
+///     // Catch-all for unknown discriminants (e.g., written by a newer version),
+///     // holding the raw value of the entire enum. Requires at least one unused
+///     // discriminant. Unpacking never produces this variant for known discriminants,
+///     // and packing panics if its value has a known discriminant.
+///     #[ribbit(size = 16, non_exhaustive)]
+///     Unknown(u16),
+/// }
+/// # }
+/// ```
+///
 /// **Field attributes**.
 ///
 /// These can be used on enum variant and struct fields.
@@ -261,6 +373,35 @@
 ///     )]
 ///     field: NonZeroU8,
 /// }
+///
+/// #[derive(ribbit::Pack, Copy, Clone)]
+/// #[ribbit(size = 13)]
+/// struct Optional {
+///     // Encode `Option<T>` as a presence bit above the bits of `T`, for types
+///     // without a niche. The size attribute (if any) includes the presence bit.
+///     #[ribbit(option = "flag")]
+///     field: Option<ribbit::u12>,
+/// }
+///
+/// #[derive(ribbit::Pack, Copy, Clone)]
+/// #[ribbit(size = 32)]
+/// struct Array {
+///     // Encode `[T; N]` as `N` consecutive elements, starting from the least
+///     // significant bits. The size attribute (if any) covers the whole array.
+///     field: [ribbit::u4; 8],
+/// }
+///
+/// #[derive(ribbit::Pack, Copy, Clone)]
+/// #[ribbit(size = 32)]
+/// struct Counter {
+///     count: ribbit::u28,
+///     // Absorb carry out of the field directly below, so that `derive(Atomic)`
+///     // arithmetic on that field is native: overflow lands in the guard bits, and
+///     // carry out of the guard field falls off the end. Must be a `bool` or
+///     // integer field at the most significant bits of the loose type.
+///     #[ribbit(guard)]
+///     overflows: ribbit::u4,
+/// }
 /// # }
 /// ```
 #[doc(inline)]
@@ -806,6 +947,29 @@ pub use arbitrary_int::u98;
 #[doc(no_inline)]
 pub use arbitrary_int::u99;
 
+mod ptr;
+pub use ptr::NonNullPtr;
+pub use ptr::Ptr;
+
+mod niche;
+pub use niche::HasNiche;
+pub use niche::Niche;
+pub use niche::NichePacked;
+
+mod either;
+pub use either::Either;
+pub use either::EitherPacked;
+pub use either::ResultPacked;
+
+mod invalid;
+pub use invalid::Invalid;
+pub use invalid::InvalidBits;
+
+mod bytes;
+
+mod words;
+pub use words::Words;
+
 /// Support for atomic operations on packed representations.
 #[cfg(feature = "atomic")]
 pub mod atomic;
@@ -824,7 +988,8 @@ pub type Unpacked<T> = <T as Unpack>::Unpacked;
 
 /// Marks a type that can be packed into `BITS`.
 ///
-/// Currently supports sizes up to 128 bits.
+/// Currently supports sizes up to 512 bits, where types wider than 128 bits
+/// are represented by [`Words`].
 ///
 /// # Safety
 ///
@@ -837,7 +1002,8 @@ pub unsafe trait Pack: Copy {
 
 /// Marks a packed type with size `BITS`.
 ///
-/// Currently supports sizes up to 128 bits.
+/// Currently supports sizes up to 512 bits, where types wider than 128 bits
+/// are represented by [`Words`].
 ///
 /// # Safety
 ///
@@ -857,9 +1023,24 @@ pub unsafe trait Unpack: Copy {
     /// Smallest native unsigned integer type that fits `Self::Raw`.
     type Loose: Loose;
 
+    /// Smallest native unsigned integer type that fits `Self::Raw` and a tag bit,
+    /// which is the loose type of [`Either`] and [`Result`] with this payload.
+    ///
+    /// Saturates at the largest native integer type, and can be wider than
+    /// necessary when `BITS` depends on generic parameters.
+    type Tagged: Loose;
+
     /// Raw underlying representation of a packed type.
     type Raw: Unpack<Unpacked = Self::Raw, Loose = Self::Loose, Raw = Self::Raw>;
 
+    /// Name of the unpacked type, for error messages.
+    #[doc(hidden)]
+    const NAME: &'static str;
+
+    /// Valid bit patterns, checked by the generated `try_from_raw`.
+    #[doc(hidden)]
+    const RULES: &'static [private::Rule];
+
     /// Convert to unpacked representation.
     fn unpack(self) -> Self::Unpacked;
 
@@ -883,17 +1064,74 @@ pub unsafe trait Unpack: Copy {
     unsafe fn from_raw_unchecked(raw: Self::Raw) -> Self;
 }
 
-/// Native unsigned integer type.
+/// Native unsigned integer type, or [`Words`] for packed types wider than 128 bits.
 ///
 /// Used internally for `const`-compatible operations on the underlying bytes
 /// of a packed type.
 pub trait Loose:
-    Copy + Sized + Unpack<Unpacked = Self, Loose = Self, Raw = Self> + seal::Seal
+    Copy
+    + Sized
+    + Eq
+    + core::hash::Hash
+    + core::ops::BitAnd<Output = Self>
+    + core::ops::BitOr<Output = Self>
+    + core::ops::Shl<u32, Output = Self>
+    + core::ops::Shr<u32, Output = Self>
+    + Unpack<Unpacked = Self, Loose = Self, Raw = Self>
+    + seal::Seal
 {
     const ZERO: Self;
-    /// Default atomic integer type.
-    #[cfg(feature = "atomic")]
-    type Atomic: atomic::Raw<Self>;
+    const ONE: Self;
+    /// Whether `Self` is [`Words`] rather than a native integer type.
+    #[doc(hidden)]
+    const WORDS: bool;
+}
+
+/// Larger of the native integer types `Self` and `R`.
+///
+/// Sealed, since [`Loose`] is sealed.
+pub trait Max<R: Loose>: Loose {
+    type Output: Loose;
+}
+
+macro_rules! impl_max {
+    ($($l:ty, $r:ty => $output:ty;)*) => {
+        $(impl Max<$r> for $l {
+            type Output = $output;
+        })*
+    };
+}
+
+impl_max! {
+    u8, u8 => u8;
+    u8, u16 => u16;
+    u8, u32 => u32;
+    u8, u64 => u64;
+    u16, u8 => u16;
+    u16, u16 => u16;
+    u16, u32 => u32;
+    u16, u64 => u64;
+    u32, u8 => u32;
+    u32, u16 => u32;
+    u32, u32 => u32;
+    u32, u64 => u64;
+    u64, u8 => u64;
+    u64, u16 => u64;
+    u64, u32 => u64;
+    u64, u64 => u64;
+}
+
+#[cfg(feature = "u128")]
+impl_max! {
+    u8, u128 => u128;
+    u16, u128 => u128;
+    u32, u128 => u128;
+    u64, u128 => u128;
+    u128, u8 => u128;
+    u128, u16 => u128;
+    u128, u32 => u128;
+    u128, u64 => u128;
+    u128, u128 => u128;
 }
 
 mod seal {
@@ -957,6 +1195,8 @@ pub mod convert {
     #[doc(hidden)]
     #[inline]
     pub const unsafe fn loose_to_packed<T: Unpack>(loose: T::Loose) -> T {
+        crate::private::check::<T>(loose);
+
         // Zero sized types are the only types where the
         // loose and packed representations differ.
         if const { T::BITS == 0 } {
@@ -984,7 +1224,13 @@ pub mod convert {
         const { assert_size_align::<T::Raw, T>() }
 
         // SAFETY: `T` and `T::Raw` have the same layout, and caller guarantees bit pattern is valid
-        unsafe { Transmute { raw }.packed }
+        unsafe {
+            let transmute = Transmute { raw };
+            if T::BITS > 0 {
+                crate::private::check::<T>(transmute.loose);
+            }
+            transmute.packed
+        }
     }
 
     #[repr(C)]
@@ -998,7 +1244,14 @@ pub mod convert {
     #[allow(private_bounds)]
     #[inline]
     pub const fn loose_to_loose<F: Loose, I: Loose>(from: F) -> I {
-        // SAFETY: `Loose` is only implemented for native integer types.
+        const {
+            assert!(
+                !F::WORDS && !I::WORDS,
+                "Cannot convert between native integer types and `Words`",
+            )
+        }
+
+        // SAFETY: checked above that both types are native integer types.
         unsafe {
             let size_from = const { core::mem::size_of::<F>() };
             let size_into = const { core::mem::size_of::<I>() };
@@ -1047,12 +1300,18 @@ macro_rules! impl_pack {
 }
 
 macro_rules! impl_unpack {
-    ($tight:ty, $bits:expr, $loose:ty) => {
+    ($tight:ty, $bits:expr, $loose:ty, $tagged:ty) => {
+        impl_unpack!($tight, $bits, $loose, $tagged, []);
+    };
+    ($tight:ty, $bits:expr, $loose:ty, $tagged:ty, $rules:expr) => {
         unsafe impl Unpack for $tight {
             const BITS: usize = $bits;
             type Unpacked = Self;
             type Loose = $loose;
+            type Tagged = $tagged;
             type Raw = Self;
+            const NAME: &'static str = stringify!($tight);
+            const RULES: &'static [private::Rule] = &$rules;
 
             #[inline]
             fn unpack(self) -> Self::Unpacked {
@@ -1082,21 +1341,21 @@ macro_rules! impl_unpack {
 
 #[rustfmt::skip]
 macro_rules! impl_impl_number {
-    ($name:ident, $unsigned_loose:ty, $signed_loose:ty, $loose_bits:expr, $dollar:tt) => {
+    ($name:ident, $unsigned_loose:ty, $signed_loose:ty, $loose_bits:expr, $tagged:ty, $dollar:tt) => {
         impl_pack!($unsigned_loose);
-        impl_unpack!($unsigned_loose, $loose_bits, $unsigned_loose);
+        impl_unpack!($unsigned_loose, $loose_bits, $unsigned_loose, $tagged);
 
         impl_pack!($signed_loose);
-        impl_unpack!($signed_loose, $loose_bits, $unsigned_loose);
+        impl_unpack!($signed_loose, $loose_bits, $unsigned_loose, $tagged);
 
         macro_rules! $name {
             ($dollar($unsigned:ident, $signed:ident: $bits:expr),* $dollar(,)?) => {
                 $dollar(
                     impl_pack!($unsigned);
-                    impl_unpack!($unsigned, $bits, $unsigned_loose);
+                    impl_unpack!($unsigned, $bits, $unsigned_loose, $unsigned_loose);
 
                     impl_pack!($signed);
-                    impl_unpack!($signed, $bits, $unsigned_loose);
+                    impl_unpack!($signed, $bits, $unsigned_loose, $unsigned_loose);
                 )*
             };
         }
@@ -1109,7 +1368,10 @@ unsafe impl Unpack for () {
     const BITS: usize = 0;
     type Unpacked = Self;
     type Loose = u8;
+    type Tagged = u8;
     type Raw = ();
+    const NAME: &'static str = "()";
+    const RULES: &'static [private::Rule] = &[];
 
     #[inline]
     fn unpack(self) -> Self::Unpacked {}
@@ -1134,7 +1396,10 @@ unsafe impl<T> Unpack for PhantomData<T> {
     const BITS: usize = 0;
     type Unpacked = PhantomData<T>;
     type Loose = u8;
+    type Tagged = u8;
     type Raw = Self;
+    const NAME: &'static str = "PhantomData";
+    const RULES: &'static [private::Rule] = &[];
 
     #[inline]
     fn unpack(self) -> Self::Unpacked {
@@ -1158,7 +1423,10 @@ unsafe impl Unpack for bool {
     const BITS: usize = 1;
     type Unpacked = bool;
     type Loose = u8;
+    type Tagged = u8;
     type Raw = bool;
+    const NAME: &'static str = "bool";
+    const RULES: &'static [private::Rule] = &[];
 
     #[inline]
     fn unpack(self) -> Self::Unpacked {
@@ -1178,11 +1446,16 @@ unsafe impl Unpack for bool {
 
 impl Loose for u8 {
     const ZERO: Self = 0;
-    #[cfg(feature = "atomic")]
+    const ONE: Self = 1;
+    const WORDS: bool = false;
+}
+
+#[cfg(feature = "atomic")]
+impl atomic::HasAtomic for u8 {
     type Atomic = atomic::AtomicU8;
 }
 
-impl_impl_number!(impl_u8, u8, i8, 8, $);
+impl_impl_number!(impl_u8, u8, i8, 8, u16, $);
 impl_u8!(
     u1, i1: 1,
     u2, i2: 2,
@@ -1195,11 +1468,16 @@ impl_u8!(
 
 impl Loose for u16 {
     const ZERO: Self = 0;
-    #[cfg(feature = "atomic")]
+    const ONE: Self = 1;
+    const WORDS: bool = false;
+}
+
+#[cfg(feature = "atomic")]
+impl atomic::HasAtomic for u16 {
     type Atomic = atomic::AtomicU16;
 }
 
-impl_impl_number!(impl_u16, u16, i16, 16, $);
+impl_impl_number!(impl_u16, u16, i16, 16, u32, $);
 impl_u16!(
     u9, i9: 9,
     u10, i10: 10,
@@ -1212,11 +1490,16 @@ impl_u16!(
 
 impl Loose for u32 {
     const ZERO: Self = 0;
-    #[cfg(feature = "atomic")]
+    const ONE: Self = 1;
+    const WORDS: bool = false;
+}
+
+#[cfg(feature = "atomic")]
+impl atomic::HasAtomic for u32 {
     type Atomic = atomic::AtomicU32;
 }
 
-impl_impl_number!(impl_u32, u32, i32, 32, $);
+impl_impl_number!(impl_u32, u32, i32, 32, u64, $);
 impl_u32!(
     u17, i17: 17,
     u18, i18: 18,
@@ -1237,11 +1520,16 @@ impl_u32!(
 
 impl Loose for u64 {
     const ZERO: Self = 0;
-    #[cfg(feature = "atomic")]
+    const ONE: Self = 1;
+    const WORDS: bool = false;
+}
+
+#[cfg(feature = "atomic")]
+impl atomic::HasAtomic for u64 {
     type Atomic = atomic::AtomicU64;
 }
 
-impl_impl_number!(impl_u64, u64, i64, 64, $);
+impl_impl_number!(impl_u64, u64, i64, 64, private::Loose128, $);
 impl_u64!(
     u33, i33: 33,
     u34, i34: 34,
@@ -1279,12 +1567,17 @@ impl_u64!(
 #[cfg(feature = "u128")]
 impl Loose for u128 {
     const ZERO: Self = 0;
-    #[cfg(feature = "atomic")]
+    const ONE: Self = 1;
+    const WORDS: bool = false;
+}
+
+#[cfg(all(feature = "u128", feature = "atomic"))]
+impl atomic::HasAtomic for u128 {
     type Atomic = atomic::AtomicU128;
 }
 
 #[cfg(feature = "u128")]
-impl_impl_number!(impl_u128, u128, i128, 128, $);
+impl_impl_number!(impl_u128, u128, i128, 128, u128, $);
 #[cfg(feature = "u128")]
 impl_u128!(
     u65, i65: 65,
@@ -1354,7 +1647,7 @@ impl_u128!(
 
 /// Marker trait asserting that values of this type cannot be zero, *and*
 /// that Rust is aware of this niche. Only implemented for standard library
-/// `NonZero` types.
+/// `NonZero` types and [`NonNullPtr`].
 ///
 /// # Safety
 ///
@@ -1364,23 +1657,35 @@ impl_u128!(
 unsafe trait NonZero {}
 
 macro_rules! impl_non_zero {
-    ($unsigned:ty, $signed:ty, $loose:ty, $bits:expr) => {
+    ($unsigned:ty, $signed:ty, $loose:ty, $tagged:ty, $bits:expr) => {
         impl_pack!($unsigned);
-        impl_unpack!($unsigned, $bits, $loose);
+        impl_unpack!(
+            $unsigned,
+            $bits,
+            $loose,
+            $tagged,
+            [private::Rule::non_zero($bits)]
+        );
         unsafe impl NonZero for $unsigned {}
 
         impl_pack!($signed);
-        impl_unpack!($signed, $bits, $loose);
+        impl_unpack!(
+            $signed,
+            $bits,
+            $loose,
+            $tagged,
+            [private::Rule::non_zero($bits)]
+        );
         unsafe impl NonZero for $signed {}
     };
 }
 
-impl_non_zero!(NonZeroU8, NonZeroI8, u8, 8);
-impl_non_zero!(NonZeroU16, NonZeroI16, u16, 16);
-impl_non_zero!(NonZeroU32, NonZeroI32, u32, 32);
-impl_non_zero!(NonZeroU64, NonZeroI64, u64, 64);
+impl_non_zero!(NonZeroU8, NonZeroI8, u8, u16, 8);
+impl_non_zero!(NonZeroU16, NonZeroI16, u16, u32, 16);
+impl_non_zero!(NonZeroU32, NonZeroI32, u32, u64, 32);
+impl_non_zero!(NonZeroU64, NonZeroI64, u64, private::Loose128, 64);
 #[cfg(feature = "u128")]
-impl_non_zero!(NonZeroU128, NonZeroI128, u128, 128);
+impl_non_zero!(NonZeroU128, NonZeroI128, u128, u128, 128);
 
 unsafe impl<T> Pack for Option<T>
 where
@@ -1400,7 +1705,13 @@ where
     const BITS: usize = T::BITS;
     type Unpacked = Option<T::Unpacked>;
     type Loose = T::Loose;
+    type Tagged = T::Tagged;
     type Raw = T::Loose;
+    const NAME: &'static str = "Option";
+    const RULES: &'static [private::Rule] = &[private::Rule::Or {
+        value: 0,
+        rules: T::RULES,
+    }];
 
     #[inline]
     fn unpack(self) -> Self::Unpacked {
@@ -1425,6 +1736,8 @@ where
             assert!(align_of::<Self>() == align_of::<T>());
         }
 
+        private::check::<Self>(raw);
+
         // SAFETY: `T::Raw` implements `NonZero`, so Option<T> uses zero niche
         unsafe { core::mem::transmute_copy(&raw) }
     }
@@ -1432,6 +1745,28 @@ where
 
 #[doc(hidden)]
 pub mod private {
+    use core::sync::atomic::Ordering;
+
+    pub use crate::bytes::to_be_bytes;
+    pub use crate::bytes::to_le_bytes;
+    pub use crate::bytes::to_ne_bytes;
+    pub use crate::bytes::try_from_be_bytes;
+    pub use crate::bytes::try_from_le_bytes;
+    pub use crate::bytes::try_from_ne_bytes;
+    pub use crate::invalid::check;
+    pub use crate::invalid::try_loose_to_packed;
+    pub use crate::invalid::Rule;
+    pub use crate::invalid::Variant;
+
+    /// Strongest valid failure ordering for a compare-and-swap with `success` ordering.
+    pub const fn failure_ordering(success: Ordering) -> Ordering {
+        match success {
+            Ordering::Release => Ordering::Relaxed,
+            Ordering::AcqRel => Ordering::Acquire,
+            ordering => ordering,
+        }
+    }
+
     /// `const` assertion that `T` is backed by a non-zero type.
     #[expect(private_bounds)]
     pub const fn assert_non_zero<T>()
@@ -1441,6 +1776,54 @@ pub mod private {
     {
     }
 
+    /// `const` assertion that enum discriminants fit in `size` bits and are unique.
+    pub const fn assert_discriminants(discriminants: &[i128], size: usize) {
+        let mut i = 0;
+        while i < discriminants.len() {
+            assert!(
+                fits(discriminants[i], size),
+                "Discriminant does not fit in discriminant bits",
+            );
+
+            let mut j = 0;
+            while j < i {
+                assert!(
+                    truncate(discriminants[i], size) != truncate(discriminants[j], size),
+                    "Discriminants are not unique",
+                );
+                j += 1;
+            }
+
+            i += 1;
+        }
+    }
+
+    /// `const` assertion that a discriminant is non-zero when truncated to `size` bits.
+    pub const fn assert_discriminant_non_zero(discriminant: i128, size: usize) {
+        assert!(
+            truncate(discriminant, size) != 0,
+            "Non-zero enum requires either all non-zero discriminants *or* variant with discriminant 0 to be non-zero",
+        )
+    }
+
+    /// Whether `discriminant` can be represented in `size` bits,
+    /// either as an unsigned or a two's complement signed integer.
+    const fn fits(discriminant: i128, size: usize) -> bool {
+        match size {
+            0 => discriminant == 0,
+            128.. => true,
+            _ if discriminant >= 0 => (discriminant as u128) < 1 << size,
+            _ => discriminant >= -(1 << (size - 1)),
+        }
+    }
+
+    const fn truncate(discriminant: i128, size: usize) -> u128 {
+        match size {
+            128.. => discriminant as u128,
+            _ => discriminant as u128 & ((1 << size) - 1),
+        }
+    }
+
     /// `const` assertion that `T` is exactly `expected` bits.
     pub const fn assert_size_eq<T>(expected: usize)
     where
@@ -1452,6 +1835,13 @@ pub mod private {
         )
     }
 
+    /// Native integer type for 65 to 128 bits, which saturates at `u64`
+    /// without the `u128` feature.
+    #[cfg(feature = "u128")]
+    pub type Loose128 = u128;
+    #[cfg(not(feature = "u128"))]
+    pub type Loose128 = u64;
+
     /// `const` assertion that `T` is at least `expected` bits.
     pub const fn assert_size_ge<T>(expected: usize)
     where
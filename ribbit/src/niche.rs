@@ -0,0 +1,193 @@
+use core::fmt::Debug;
+use core::hash::Hash;
+use core::marker::PhantomData;
+
+use crate::convert;
+use crate::private::Rule;
+use crate::Pack;
+use crate::Unpack;
+
+/// Marks a packed type with a bit pattern `NICHE` that is not a valid value.
+///
+/// The [`Pack`](`macro@crate::Pack`) macro implements this trait for enums with
+/// a spare discriminant value, so that [`Niche`] can use it to represent `None`.
+///
+/// # Safety
+///
+/// Implementer must guarantee that `NICHE` fits in `BITS`, and that it is
+/// not a valid bit pattern for `Self`.
+pub unsafe trait HasNiche: Unpack {
+    const NICHE: Self::Loose;
+}
+
+/// Optional `T` that represents `None` with the [`HasNiche::NICHE`] of `T`'s packed type,
+/// so that `Packed<Niche<T>>` has the same size as `Packed<T>`.
+///
+/// Unlike `Option<T>`, which requires a non-zero packed type, this works for
+/// any enum with a spare discriminant value.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Niche<T>(pub Option<T>);
+
+/// Packed representation of [`Niche`].
+#[repr(transparent)]
+pub struct NichePacked<T: Unpack> {
+    value: T::Loose,
+    r#type: PhantomData<T>,
+}
+
+impl<T> Niche<T> {
+    #[inline]
+    pub const fn new(value: Option<T>) -> Self {
+        Self(value)
+    }
+
+    #[inline]
+    pub fn into_option(self) -> Option<T> {
+        self.0
+    }
+}
+
+impl<T> From<Option<T>> for Niche<T> {
+    #[inline]
+    fn from(value: Option<T>) -> Self {
+        Self(value)
+    }
+}
+
+impl<T> From<Niche<T>> for Option<T> {
+    #[inline]
+    fn from(niche: Niche<T>) -> Self {
+        niche.0
+    }
+}
+
+impl<T: HasNiche> NichePacked<T> {
+    pub const NONE: Self = Self {
+        value: T::NICHE,
+        r#type: PhantomData,
+    };
+
+    #[inline]
+    pub const fn new(value: Option<T>) -> Self {
+        match value {
+            None => Self::NONE,
+            Some(value) => Self {
+                value: convert::packed_to_loose(value),
+                r#type: PhantomData,
+            },
+        }
+    }
+
+    #[inline]
+    pub fn get(self) -> Option<T> {
+        match self.is_none() {
+            true => None,
+            // SAFETY: any bit pattern other than `T::NICHE` was packed from a valid `T`
+            false => Some(unsafe { convert::loose_to_packed(self.value) }),
+        }
+    }
+
+    #[inline]
+    pub fn is_none(self) -> bool {
+        self.value == T::NICHE
+    }
+
+    #[inline]
+    pub fn is_some(self) -> bool {
+        !self.is_none()
+    }
+}
+
+impl<T: HasNiche> From<Option<T>> for NichePacked<T> {
+    #[inline]
+    fn from(value: Option<T>) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<T: HasNiche> From<NichePacked<T>> for Option<T> {
+    #[inline]
+    fn from(niche: NichePacked<T>) -> Self {
+        niche.get()
+    }
+}
+
+impl<T: Unpack> Clone for NichePacked<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Unpack> Copy for NichePacked<T> {}
+
+impl<T: HasNiche> Debug for NichePacked<T>
+where
+    T::Unpacked: Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        self.unpack().fmt(f)
+    }
+}
+
+impl<T: Unpack> PartialEq for NichePacked<T> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Unpack> Eq for NichePacked<T> {}
+
+impl<T: Unpack> Hash for NichePacked<T> {
+    #[inline]
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.value.hash(state)
+    }
+}
+
+unsafe impl<T> Pack for Niche<T>
+where
+    T: Pack,
+    T::Packed: HasNiche,
+{
+    type Packed = NichePacked<T::Packed>;
+
+    #[inline]
+    fn pack(self) -> Self::Packed {
+        NichePacked::new(self.0.map(Pack::pack))
+    }
+}
+
+unsafe impl<T: HasNiche> Unpack for NichePacked<T> {
+    const BITS: usize = T::BITS;
+    type Unpacked = Niche<T::Unpacked>;
+    type Loose = T::Loose;
+    type Tagged = T::Tagged;
+    type Raw = T::Loose;
+    const NAME: &'static str = "Niche";
+    const RULES: &'static [Rule] = &[Rule::Or {
+        value: crate::invalid::loose_to_u128(T::NICHE),
+        rules: T::RULES,
+    }];
+
+    #[inline]
+    fn unpack(self) -> Self::Unpacked {
+        Niche(self.get().map(Unpack::unpack))
+    }
+
+    #[inline]
+    fn into_raw(self) -> Self::Raw {
+        self.value
+    }
+
+    #[inline]
+    unsafe fn from_raw_unchecked(raw: Self::Raw) -> Self {
+        crate::private::check::<Self>(raw);
+        Self {
+            value: raw,
+            r#type: PhantomData,
+        }
+    }
+}
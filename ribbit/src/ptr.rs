@@ -0,0 +1,327 @@
+use core::cmp::Ordering;
+use core::fmt::Debug;
+use core::hash::Hash;
+use core::marker::PhantomData;
+use core::num::NonZeroU64;
+use core::ptr::NonNull;
+
+use crate::NonZero;
+use crate::Pack;
+use crate::Unpack;
+
+/// Pointer to `T` packed into `ADDR_BITS - ALIGN_BITS` bits.
+///
+/// The low `ALIGN_BITS` bits of the address are zero by alignment, and the high
+/// `64 - ADDR_BITS` bits are assumed to be zero (e.g., 48-bit virtual addresses on x86-64),
+/// so neither is stored. This frees up space in the surrounding packed type for tags
+/// or counters (e.g., to prevent the ABA problem in lock-free data structures).
+///
+/// Like [`core::sync::atomic::AtomicPtr`], this type is [`Send`] and [`Sync`] regardless
+/// of `T`, since dereferencing it is `unsafe` anyway.
+#[repr(transparent)]
+pub struct Ptr<T, const ALIGN_BITS: usize, const ADDR_BITS: usize> {
+    value: u64,
+    r#type: PhantomData<*mut T>,
+}
+
+/// Non-null version of [`Ptr`], so that `Option<NonNullPtr<..>>` has the same size.
+#[repr(transparent)]
+pub struct NonNullPtr<T, const ALIGN_BITS: usize, const ADDR_BITS: usize> {
+    value: NonZeroU64,
+    r#type: PhantomData<*mut T>,
+}
+
+const fn assert_bits<T>(align_bits: usize, addr_bits: usize) {
+    assert!(
+        align_bits <= addr_bits,
+        "ALIGN_BITS must be at most ADDR_BITS"
+    );
+    assert!(
+        addr_bits <= usize::BITS as usize,
+        "ADDR_BITS must be at most the pointer width",
+    );
+    assert!(
+        1 << align_bits <= core::mem::align_of::<T>(),
+        "ALIGN_BITS must be at most the alignment of T",
+    );
+}
+
+/// Compress the address of `ptr`, or return `None` if any of the
+/// unstored low or high bits are set.
+#[inline]
+fn compress<T>(ptr: *mut T, align_bits: usize, addr_bits: usize) -> Option<u64> {
+    let address = ptr.expose_provenance() as u64;
+    let value = address >> align_bits;
+
+    let high = value
+        .checked_shr((addr_bits - align_bits) as u32)
+        .unwrap_or(0);
+
+    match value << align_bits == address && high == 0 {
+        true => Some(value),
+        false => None,
+    }
+}
+
+#[inline]
+fn decompress<T>(value: u64, align_bits: usize) -> *mut T {
+    core::ptr::with_exposed_provenance_mut((value << align_bits) as usize)
+}
+
+impl<T, const ALIGN_BITS: usize, const ADDR_BITS: usize> Ptr<T, ALIGN_BITS, ADDR_BITS> {
+    /// Null pointer.
+    #[inline]
+    pub const fn null() -> Self {
+        const { assert_bits::<T>(ALIGN_BITS, ADDR_BITS) }
+        Self {
+            value: 0,
+            r#type: PhantomData,
+        }
+    }
+
+    /// Returns `None` if `ptr` is not aligned to `1 << ALIGN_BITS`,
+    /// or its address does not fit in `ADDR_BITS`.
+    #[inline]
+    pub fn new(ptr: *mut T) -> Option<Self> {
+        const { assert_bits::<T>(ALIGN_BITS, ADDR_BITS) }
+        compress(ptr, ALIGN_BITS, ADDR_BITS).map(|value| Self {
+            value,
+            r#type: PhantomData,
+        })
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the address of `value` does not fit in `ADDR_BITS`.
+    #[inline]
+    pub fn from_ref(value: &T) -> Self {
+        Self::new(core::ptr::from_ref(value).cast_mut()).expect("Address does not fit in ADDR_BITS")
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the address of `value` does not fit in `ADDR_BITS`.
+    #[inline]
+    pub fn from_mut(value: &mut T) -> Self {
+        Self::new(value).expect("Address does not fit in ADDR_BITS")
+    }
+
+    #[inline]
+    pub fn as_ptr(self) -> *mut T {
+        decompress(self.value, ALIGN_BITS)
+    }
+
+    #[inline]
+    pub const fn is_null(self) -> bool {
+        self.value == 0
+    }
+
+    /// # Safety
+    ///
+    /// Same as [`pointer::as_ref`](https://doc.rust-lang.org/std/primitive.pointer.html#method.as_ref).
+    #[inline]
+    pub unsafe fn as_ref<'a>(self) -> Option<&'a T> {
+        // SAFETY: caller guarantees pointer is valid
+        unsafe { self.as_ptr().as_ref() }
+    }
+}
+
+impl<T, const ALIGN_BITS: usize, const ADDR_BITS: usize> NonNullPtr<T, ALIGN_BITS, ADDR_BITS> {
+    /// Returns `None` if `ptr` is not aligned to `1 << ALIGN_BITS`,
+    /// or its address does not fit in `ADDR_BITS`.
+    #[inline]
+    pub fn new(ptr: NonNull<T>) -> Option<Self> {
+        Ptr::new(ptr.as_ptr()).and_then(Self::from_ptr)
+    }
+
+    /// Returns `None` if `ptr` is null.
+    #[inline]
+    pub const fn from_ptr(ptr: Ptr<T, ALIGN_BITS, ADDR_BITS>) -> Option<Self> {
+        match NonZeroU64::new(ptr.value) {
+            Some(value) => Some(Self {
+                value,
+                r#type: PhantomData,
+            }),
+            None => None,
+        }
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the address of `value` does not fit in `ADDR_BITS`.
+    #[inline]
+    pub fn from_ref(value: &T) -> Self {
+        Self::new(NonNull::from(value)).expect("Address does not fit in ADDR_BITS")
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the address of `value` does not fit in `ADDR_BITS`.
+    #[inline]
+    pub fn from_mut(value: &mut T) -> Self {
+        Self::new(NonNull::from(value)).expect("Address does not fit in ADDR_BITS")
+    }
+
+    #[inline]
+    pub fn as_ptr(self) -> *mut T {
+        decompress(self.value.get(), ALIGN_BITS)
+    }
+
+    #[inline]
+    pub fn as_non_null(self) -> NonNull<T> {
+        // SAFETY: compressed address is non-zero, so decompressed address is non-zero
+        unsafe { NonNull::new_unchecked(self.as_ptr()) }
+    }
+
+    #[inline]
+    pub const fn into_ptr(self) -> Ptr<T, ALIGN_BITS, ADDR_BITS> {
+        Ptr {
+            value: self.value.get(),
+            r#type: PhantomData,
+        }
+    }
+}
+
+impl<T, const ALIGN_BITS: usize, const ADDR_BITS: usize> From<NonNullPtr<T, ALIGN_BITS, ADDR_BITS>>
+    for Ptr<T, ALIGN_BITS, ADDR_BITS>
+{
+    #[inline]
+    fn from(ptr: NonNullPtr<T, ALIGN_BITS, ADDR_BITS>) -> Self {
+        ptr.into_ptr()
+    }
+}
+
+macro_rules! impl_ptr {
+    ($ptr:ident, $raw:ty, $rules:expr) => {
+        // SAFETY: only dereferencing the pointer is unsafe
+        unsafe impl<T, const ALIGN_BITS: usize, const ADDR_BITS: usize> Send
+            for $ptr<T, ALIGN_BITS, ADDR_BITS>
+        {
+        }
+
+        // SAFETY: only dereferencing the pointer is unsafe
+        unsafe impl<T, const ALIGN_BITS: usize, const ADDR_BITS: usize> Sync
+            for $ptr<T, ALIGN_BITS, ADDR_BITS>
+        {
+        }
+
+        impl<T, const ALIGN_BITS: usize, const ADDR_BITS: usize> Clone
+            for $ptr<T, ALIGN_BITS, ADDR_BITS>
+        {
+            #[inline]
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<T, const ALIGN_BITS: usize, const ADDR_BITS: usize> Copy
+            for $ptr<T, ALIGN_BITS, ADDR_BITS>
+        {
+        }
+
+        impl<T, const ALIGN_BITS: usize, const ADDR_BITS: usize> Debug
+            for $ptr<T, ALIGN_BITS, ADDR_BITS>
+        {
+            #[inline]
+            fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+                core::fmt::Pointer::fmt(&self.as_ptr(), f)
+            }
+        }
+
+        impl<T, const ALIGN_BITS: usize, const ADDR_BITS: usize> PartialEq
+            for $ptr<T, ALIGN_BITS, ADDR_BITS>
+        {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                self.value == other.value
+            }
+        }
+
+        impl<T, const ALIGN_BITS: usize, const ADDR_BITS: usize> Eq
+            for $ptr<T, ALIGN_BITS, ADDR_BITS>
+        {
+        }
+
+        impl<T, const ALIGN_BITS: usize, const ADDR_BITS: usize> PartialOrd
+            for $ptr<T, ALIGN_BITS, ADDR_BITS>
+        {
+            #[inline]
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+
+        impl<T, const ALIGN_BITS: usize, const ADDR_BITS: usize> Ord
+            for $ptr<T, ALIGN_BITS, ADDR_BITS>
+        {
+            #[inline]
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.value.cmp(&other.value)
+            }
+        }
+
+        impl<T, const ALIGN_BITS: usize, const ADDR_BITS: usize> Hash
+            for $ptr<T, ALIGN_BITS, ADDR_BITS>
+        {
+            #[inline]
+            fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+                self.value.hash(state)
+            }
+        }
+
+        unsafe impl<T, const ALIGN_BITS: usize, const ADDR_BITS: usize> Pack
+            for $ptr<T, ALIGN_BITS, ADDR_BITS>
+        {
+            type Packed = Self;
+
+            #[inline]
+            fn pack(self) -> Self::Packed {
+                self
+            }
+        }
+
+        unsafe impl<T, const ALIGN_BITS: usize, const ADDR_BITS: usize> Unpack
+            for $ptr<T, ALIGN_BITS, ADDR_BITS>
+        {
+            const BITS: usize = ADDR_BITS - ALIGN_BITS;
+            type Unpacked = Self;
+            type Loose = u64;
+            type Tagged = crate::private::Loose128;
+            type Raw = Self;
+            const NAME: &'static str = stringify!($ptr);
+            const RULES: &'static [crate::private::Rule] = &$rules;
+
+            #[inline]
+            fn unpack(self) -> Self::Unpacked {
+                const {
+                    assert!(core::mem::size_of::<Self>() == core::mem::size_of::<$raw>());
+                    assert!(core::mem::align_of::<Self>() == core::mem::align_of::<$raw>());
+                }
+
+                self
+            }
+
+            #[inline]
+            fn into_raw(self) -> Self::Raw {
+                self
+            }
+
+            #[inline]
+            unsafe fn from_raw_unchecked(raw: Self::Raw) -> Self {
+                raw
+            }
+        }
+    };
+}
+
+impl_ptr!(Ptr, u64, []);
+impl_ptr!(
+    NonNullPtr,
+    NonZeroU64,
+    [crate::private::Rule::non_zero(ADDR_BITS - ALIGN_BITS)]
+);
+
+unsafe impl<T, const ALIGN_BITS: usize, const ADDR_BITS: usize> NonZero
+    for NonNullPtr<T, ALIGN_BITS, ADDR_BITS>
+{
+}
@@ -0,0 +1,306 @@
+use core::cmp::Ordering;
+
+use crate::private::Rule;
+use crate::Loose;
+use crate::Pack;
+use crate::Unpack;
+
+/// Array of `N` little-endian `u64` words, backing packed types wider than 128 bits.
+///
+/// Word `0` holds the least significant bits, and each word is stored in native
+/// byte order. Fields can straddle word boundaries, so [`Words::bits`] and
+/// [`Words::with_bits`] address bits instead of words.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Words<const N: usize>([u64; N]);
+
+impl<const N: usize> Words<N> {
+    /// Total number of bits.
+    pub const BITS: usize = N * 64;
+
+    #[inline]
+    pub const fn new(words: [u64; N]) -> Self {
+        Self(words)
+    }
+
+    #[inline]
+    pub const fn value(self) -> [u64; N] {
+        self.0
+    }
+
+    /// Get `size <= 128` bits at `offset`, zero-extended to `u128`.
+    #[inline]
+    pub const fn bits(self, offset: usize, size: usize) -> u128 {
+        assert!(size <= 128 && offset + size <= Self::BITS);
+
+        let mut value = 0;
+        let mut shift = 0;
+        while shift < size {
+            let index = (offset + shift) / 64;
+            let start = (offset + shift) % 64;
+            let len = min(64 - start, size - shift);
+
+            value |= (((self.0[index] >> start) & mask(len)) as u128) << shift;
+            shift += len;
+        }
+        value
+    }
+
+    /// Replace `size <= 128` bits at `offset` with the low bits of `value`.
+    #[inline]
+    pub const fn with_bits(mut self, offset: usize, size: usize, value: u128) -> Self {
+        assert!(size <= 128 && offset + size <= Self::BITS);
+
+        let mut shift = 0;
+        while shift < size {
+            let index = (offset + shift) / 64;
+            let start = (offset + shift) % 64;
+            let len = min(64 - start, size - shift);
+            let mask = mask(len) << start;
+
+            let bits = ((value >> shift) as u64) << start;
+            self.0[index] = (self.0[index] & !mask) | (bits & mask);
+            shift += len;
+        }
+        self
+    }
+
+    /// Get `size` bits at `offset`, zero-extended to `M` words.
+    #[inline]
+    pub const fn words<const M: usize>(self, offset: usize, size: usize) -> Words<M> {
+        assert!(size <= M * 64);
+
+        let mut words = Words([0; M]);
+        let mut index = 0;
+        while index * 64 < size {
+            let len = min(64, size - index * 64);
+            words.0[index] = self.bits(offset + index * 64, len) as u64;
+            index += 1;
+        }
+        words
+    }
+
+    /// Replace `size` bits at `offset` with the low bits of `words`.
+    #[inline]
+    pub const fn with_words<const M: usize>(
+        mut self,
+        offset: usize,
+        size: usize,
+        words: Words<M>,
+    ) -> Self {
+        assert!(size <= M * 64);
+
+        let mut index = 0;
+        while index * 64 < size {
+            let len = min(64, size - index * 64);
+            self = self.with_bits(offset + index * 64, len, words.0[index] as u128);
+            index += 1;
+        }
+        self
+    }
+}
+
+const fn min(a: usize, b: usize) -> usize {
+    if a < b {
+        a
+    } else {
+        b
+    }
+}
+
+const fn mask(size: usize) -> u64 {
+    match size {
+        64.. => u64::MAX,
+        _ => (1 << size) - 1,
+    }
+}
+
+impl<const N: usize> PartialOrd for Words<N> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Numeric order, comparing the most significant words first.
+impl<const N: usize> Ord for Words<N> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl<const N: usize> core::ops::BitAnd for Words<N> {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(mut self, rhs: Self) -> Self {
+        self.0.iter_mut().zip(rhs.0).for_each(|(l, r)| *l &= r);
+        self
+    }
+}
+
+impl<const N: usize> core::ops::BitOr for Words<N> {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(mut self, rhs: Self) -> Self {
+        self.0.iter_mut().zip(rhs.0).for_each(|(l, r)| *l |= r);
+        self
+    }
+}
+
+impl<const N: usize> core::ops::Shl<u32> for Words<N> {
+    type Output = Self;
+
+    #[inline]
+    fn shl(self, by: u32) -> Self {
+        let (skip, by) = (by as usize / 64, by % 64);
+        let mut words = [0; N];
+        for (index, word) in words.iter_mut().enumerate().skip(skip) {
+            let low = index - skip;
+            *word = self.0[low] << by;
+            if by > 0 && low > 0 {
+                *word |= self.0[low - 1] >> (64 - by);
+            }
+        }
+        Self(words)
+    }
+}
+
+impl<const N: usize> core::ops::Shr<u32> for Words<N> {
+    type Output = Self;
+
+    #[inline]
+    fn shr(self, by: u32) -> Self {
+        let (skip, by) = (by as usize / 64, by % 64);
+        let mut words = [0; N];
+        for (index, word) in words.iter_mut().enumerate() {
+            let Some(high) = self.0.get(index + skip) else {
+                break;
+            };
+            *word = high >> by;
+            if let Some(next) = self.0.get(index + skip + 1).filter(|_| by > 0) {
+                *word |= next << (64 - by);
+            }
+        }
+        Self(words)
+    }
+}
+
+unsafe impl<const N: usize> Pack for Words<N> {
+    type Packed = Self;
+
+    #[inline]
+    fn pack(self) -> Self::Packed {
+        self
+    }
+}
+
+unsafe impl<const N: usize> Unpack for Words<N> {
+    const BITS: usize = N * 64;
+    type Unpacked = Self;
+    type Loose = Self;
+    type Tagged = Self;
+    type Raw = Self;
+    const NAME: &'static str = "Words";
+    const RULES: &'static [Rule] = &[];
+
+    #[inline]
+    fn unpack(self) -> Self::Unpacked {
+        self
+    }
+
+    #[inline]
+    fn into_raw(self) -> Self::Raw {
+        self
+    }
+
+    #[inline]
+    unsafe fn from_raw_unchecked(raw: Self::Raw) -> Self {
+        raw
+    }
+}
+
+impl<const N: usize> Loose for Words<N> {
+    const ZERO: Self = Self([0; N]);
+    const ONE: Self = {
+        let mut words = [0; N];
+        words[0] = 1;
+        Self(words)
+    };
+    const WORDS: bool = true;
+}
+
+impl<const N: usize> crate::seal::Seal for Words<N> {}
+
+/// Bits of a loose value, as seen by `const` validation and serialization.
+#[derive(Copy, Clone)]
+pub(crate) enum View<'a> {
+    Native(u128),
+    Words(&'a [u64]),
+}
+
+impl<'a> View<'a> {
+    pub(crate) const fn new<L: Loose>(loose: &'a L) -> Self {
+        match L::WORDS {
+            // SAFETY: `Words<N>` is `repr(transparent)` over `[u64; N]`
+            true => View::Words(unsafe {
+                core::slice::from_raw_parts(
+                    (loose as *const L).cast::<u64>(),
+                    core::mem::size_of::<L>() / 8,
+                )
+            }),
+            false => View::Native(crate::invalid::loose_to_u128(*loose)),
+        }
+    }
+
+    /// Get `size <= 128` bits at `offset`, where bits past the end are zero.
+    pub(crate) const fn extract(&self, offset: usize, size: usize) -> u128 {
+        let value = match self {
+            View::Native(value) => value.unbounded_shr(offset as u32),
+            View::Words(words) => {
+                let mut value = 0u128;
+                let mut shift = 0;
+                while shift < size {
+                    let index = (offset + shift) / 64;
+                    let start = (offset + shift) % 64;
+                    if index >= words.len() {
+                        break;
+                    }
+
+                    value |= ((words[index] >> start) as u128).unbounded_shl(shift as u32);
+                    shift += 64 - start;
+                }
+                value
+            }
+        };
+
+        match size {
+            128.. => value,
+            _ => value & ((1 << size) - 1),
+        }
+    }
+
+    /// Offset of the first set bit of `size` bits at `offset`.
+    pub(crate) const fn first_set(&self, offset: usize, size: usize) -> Option<usize> {
+        let mut shift = 0;
+        while shift < size {
+            let len = min(128, size - shift);
+            let bits = self.extract(offset + shift, len);
+            if bits != 0 {
+                return Some(offset + shift + bits.trailing_zeros() as usize);
+            }
+            shift += len;
+        }
+        None
+    }
+
+    /// Number of bits.
+    pub(crate) const fn len(&self) -> usize {
+        match self {
+            View::Native(_) => 128,
+            View::Words(words) => words.len() * 64,
+        }
+    }
+}
@@ -0,0 +1,179 @@
+use core::num::NonZeroU8;
+
+use ribbit::i4;
+use ribbit::u4;
+use ribbit::u60;
+use ribbit::Invalid;
+use ribbit::Pack as _;
+use ribbit::Unpack as _;
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 32, derive(Debug, Eq))]
+struct Nibbles {
+    nibbles: [u4; 8],
+}
+
+#[test]
+fn nibbles() {
+    let nibbles = Nibbles {
+        nibbles: core::array::from_fn(|i| u4::new(i as u8)),
+    }
+    .pack();
+
+    assert_eq!(nibbles.into_raw(), 0x7654_3210);
+    assert_eq!(nibbles.nibbles(0), u4::new(0));
+    assert_eq!(nibbles.nibbles(5), u4::new(5));
+    assert_eq!(
+        nibbles.nibbles_array(),
+        core::array::from_fn(|i| u4::new(i as u8))
+    );
+
+    let nibbles = nibbles.with_nibbles(3, u4::new(0xf));
+    assert_eq!(nibbles.into_raw(), 0x7654_f210);
+    assert_eq!(nibbles.nibbles(3), u4::new(0xf));
+
+    let nibbles = nibbles.with_nibbles_array([u4::new(1); 8]);
+    assert_eq!(nibbles.into_raw(), 0x1111_1111);
+    assert_eq!(nibbles.unpack().nibbles, [u4::new(1); 8]);
+}
+
+#[test]
+#[should_panic(expected = "array index out of bounds")]
+fn out_of_bounds() {
+    let _ = Nibbles {
+        nibbles: [u4::new(0); 8],
+    }
+    .pack()
+    .nibbles(8);
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug)]
+#[ribbit(size = 16)]
+struct Flags([bool; 16]);
+
+#[test]
+fn flags() {
+    let mut flags = [false; 16];
+    flags[1] = true;
+    flags[15] = true;
+
+    let packed = Flags(flags).pack();
+    assert_eq!(packed.into_raw(), 0x8002);
+    assert!(packed._0(1));
+    assert!(!packed._0(2));
+    assert_eq!(packed.with_0(1, false).with_0(2, true).into_raw(), 0x8004);
+    assert_eq!(packed._0_array(), flags);
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 8, derive(Debug, Eq))]
+enum Kind {
+    A,
+    #[ribbit(size = 4)]
+    B(u4),
+    C,
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 56, derive(Debug, Eq))]
+struct Mixed {
+    tag: u8,
+    #[ribbit(size = 16)]
+    kinds: [crate::Kind; 2],
+    signed: [i4; 4],
+    non_zero: [NonZeroU8; 2],
+}
+
+fn mixed() -> Mixed {
+    Mixed {
+        tag: 0xab,
+        kinds: [Kind::B(u4::new(3)), Kind::C],
+        signed: [i4::new(1), i4::new(2), i4::new(3), i4::new(4)],
+        non_zero: [NonZeroU8::new(5).unwrap(), NonZeroU8::new(6).unwrap()],
+    }
+}
+
+#[test]
+fn mixed_round_trip() {
+    let mixed = mixed();
+    let packed = mixed.pack();
+
+    assert_eq!(packed.unpack(), mixed);
+    assert_eq!(packed.tag(), 0xab);
+    assert_eq!(packed.kinds(0).unpack(), Kind::B(u4::new(3)));
+    assert_eq!(packed.kinds(1).unpack(), Kind::C);
+    assert_eq!(packed.signed(2), i4::new(3));
+    assert_eq!(packed.non_zero(1).get(), 6);
+
+    let packed = packed.with_kinds(1, Kind::A.pack());
+    assert_eq!(
+        packed.kinds_array().map(|kind| kind.unpack()),
+        [Kind::B(u4::new(3)), Kind::A]
+    );
+
+    // Sign extension must not clobber neighboring elements
+    let packed = packed.with_signed(1, i4::new(-1));
+    assert_eq!(packed.signed(0), i4::new(1));
+    assert_eq!(packed.signed(1), i4::new(-1));
+    assert_eq!(packed.signed(2), i4::new(3));
+    assert_eq!(packed.tag(), 0xab);
+    assert_eq!(packed.into_raw().value() >> 24 & 0xffff, 0x43f1);
+
+    let signed = [i4::new(-8), i4::new(-1), i4::new(0), i4::new(7)];
+    let packed = packed.with_signed_array(signed);
+    assert_eq!(packed.signed_array(), signed);
+    assert_eq!(packed.unpack().signed, signed);
+}
+
+#[test]
+fn mixed_try_from_raw() {
+    let raw = mixed().pack().into_raw().value();
+
+    // Discriminant of `kinds[1]` at bits 16..18
+    let error = ribbit::Packed::<Mixed>::try_from(raw | 0b11 << 16).unwrap_err();
+    assert_eq!(error.kind(), Invalid::Discriminant);
+    assert_eq!(error.name(), "Kind");
+    assert_eq!(error.offset(), 16);
+
+    let error = ribbit::Packed::<Mixed>::try_from(raw & !(0xff << 48)).unwrap_err();
+    assert_eq!(error.kind(), Invalid::Zero);
+    assert_eq!(error.name(), "non_zero[1]");
+    assert_eq!(error.offset(), 48);
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 188, derive(Debug, Eq))]
+struct Wide {
+    a: u60,
+    // Bits 60..124
+    bytes: [u8; 8],
+    c: u64,
+}
+
+#[test]
+fn wide() {
+    let wide = Wide {
+        a: u60::new(0),
+        bytes: [1, 2, 3, 4, 5, 6, 7, 8],
+        c: u64::MAX,
+    }
+    .pack();
+
+    assert_eq!(wide.bytes(0), 1);
+    assert_eq!(wide.bytes(7), 8);
+    assert_eq!(wide.into_raw().value()[0], 0x1 << 60);
+
+    let wide = wide.with_bytes(0, 0xff).with_bytes(7, 0);
+    assert_eq!(wide.bytes_array(), [0xff, 2, 3, 4, 5, 6, 7, 0]);
+    assert_eq!(wide.a(), u60::new(0));
+    assert_eq!(wide.c(), u64::MAX);
+    assert_eq!(wide.into_raw().value()[0], 0xf << 60);
+}
+
+const NIBBLES: ribbit::Packed<Nibbles> = ribbit::Packed::<Nibbles>::new([u4::new(2); 8]);
+const NIBBLE: u4 = NIBBLES.with_nibbles(7, u4::new(9)).nibbles(7);
+
+#[test]
+fn r#const() {
+    assert_eq!(NIBBLE, u4::new(9));
+}
@@ -1,11 +1,20 @@
-#![cfg(feature = "atomic")]
+#![cfg(all(feature = "atomic", not(all(loom, feature = "loom"))))]
 
 use core::sync::atomic::Ordering;
 
+use ribbit::i12;
+use ribbit::i7;
+use ribbit::u1;
+use ribbit::u11;
+use ribbit::u12;
 use ribbit::u22;
+use ribbit::u24;
 use ribbit::u26;
+use ribbit::u4;
+use ribbit::u6;
 use ribbit::u9;
 use ribbit::Atomic;
+use ribbit::Integer as _;
 use ribbit::Pack as _;
 use ribbit::Unpack as _;
 
@@ -153,3 +162,440 @@ fn mutable() {
 
     assert_eq!(mutable.get_mut_packed().unpack(), b);
 }
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 16, derive(Debug, Eq))]
+struct State {
+    ready: bool,
+    #[ribbit(offset = 8)]
+    epoch: u8,
+}
+
+static STATE: Atomic<State> =
+    Atomic::<State>::new_packed_const(ribbit::Packed::<State>::new(false, 3));
+
+#[test]
+fn new_packed_const() {
+    assert_eq!(
+        STATE.swap(
+            State {
+                ready: true,
+                epoch: 4
+            },
+            Ordering::Relaxed
+        ),
+        State {
+            ready: false,
+            epoch: 3
+        }
+    );
+}
+
+#[test]
+fn pointer() {
+    let mut buffer = [
+        ribbit::Packed::<State>::new(false, 1).into_raw(),
+        ribbit::Packed::<State>::new(true, 2).into_raw(),
+    ];
+
+    // SAFETY: buffer is aligned, initialized with valid bit patterns, and outlives `states`
+    let states = buffer
+        .each_mut()
+        .map(|loose| unsafe { Atomic::<State>::from_ptr(loose) });
+
+    states[0].store(
+        State {
+            ready: true,
+            epoch: 5,
+        },
+        Ordering::Relaxed,
+    );
+    assert_eq!(
+        states[1].load(Ordering::Relaxed),
+        State {
+            ready: true,
+            epoch: 2
+        }
+    );
+    assert_eq!(states[1].as_ptr().cast_const(), &raw const buffer[1]);
+    assert_eq!(buffer[0], ribbit::Packed::<State>::new(true, 5).into_raw());
+
+    // SAFETY: buffer is initialized with valid bit patterns
+    let state = unsafe { Atomic::<State>::from_mut(&mut buffer[1]) };
+    state.set(State {
+        ready: false,
+        epoch: 7,
+    });
+    assert_eq!(buffer[1], ribbit::Packed::<State>::new(false, 7).into_raw());
+}
+
+#[test]
+fn into_inner() {
+    let state = State {
+        ready: true,
+        epoch: 9,
+    };
+    assert_eq!(Atomic::<State>::new(state).into_packed(), state.pack());
+    assert_eq!(Atomic::<State>::new(state).into_inner(), state);
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 32, derive(Debug, Eq))]
+struct Counter {
+    count: u16,
+    epoch: u16,
+}
+
+#[test]
+fn fetch_update() {
+    let counter = Atomic::<Counter>::new(Counter { count: 0, epoch: 1 });
+
+    assert_eq!(
+        counter.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |counter| {
+            Some(Counter {
+                count: counter.count + 1,
+                ..counter
+            })
+        }),
+        Ok(Counter { count: 0, epoch: 1 }),
+    );
+
+    assert_eq!(
+        counter.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |_| None),
+        Err(Counter { count: 1, epoch: 1 }),
+    );
+
+    assert_eq!(
+        counter.fetch_update_packed(Ordering::Relaxed, Ordering::Relaxed, |counter| {
+            Some(counter.with_epoch(counter.epoch() + 1))
+        }),
+        Ok(Counter { count: 1, epoch: 1 }.pack()),
+    );
+
+    assert_eq!(
+        counter.load(Ordering::Relaxed),
+        Counter { count: 1, epoch: 2 }
+    );
+}
+
+#[test]
+fn update() {
+    let counter = Atomic::<Counter>::new(Counter { count: 3, epoch: 0 });
+
+    assert_eq!(
+        counter.update(Ordering::Relaxed, Ordering::Relaxed, |counter| Counter {
+            count: counter.count * 2,
+            ..counter
+        }),
+        Counter { count: 3, epoch: 0 },
+    );
+
+    assert_eq!(
+        counter.update_packed(Ordering::Relaxed, Ordering::Relaxed, |counter| {
+            counter.with_epoch(7)
+        }),
+        Counter { count: 6, epoch: 0 }.pack(),
+    );
+
+    assert_eq!(
+        counter.load(Ordering::Relaxed),
+        Counter { count: 6, epoch: 7 }
+    );
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 32, derive(Debug, Eq, Atomic))]
+struct Fields {
+    lo: u9,
+    #[ribbit(with(rename = "set_mid"))]
+    mid: bool,
+    hi: u22,
+}
+
+#[test]
+fn fields() {
+    let fields = Atomic::<Fields>::new(Fields {
+        lo: u9::new(5),
+        mid: false,
+        hi: u22::new(10),
+    });
+
+    assert_eq!(fields.load_lo(Ordering::Relaxed), u9::new(5));
+    assert!(!fields.load_mid(Ordering::Relaxed));
+    assert_eq!(fields.load_hi(Ordering::Relaxed), u22::new(10));
+
+    fields.store_mid(true, Ordering::Relaxed);
+    assert_eq!(fields.swap_lo(u9::new(7), Ordering::AcqRel), u9::new(5));
+
+    assert_eq!(
+        fields.fetch_update_hi(Ordering::Release, Ordering::Relaxed, |hi| {
+            Some(u22::new(hi.value() + 1))
+        }),
+        Ok(u22::new(10)),
+    );
+
+    assert_eq!(
+        fields.fetch_update_hi(Ordering::Release, Ordering::Relaxed, |_| None),
+        Err(u22::new(11)),
+    );
+
+    assert_eq!(
+        fields.load(Ordering::Relaxed),
+        Fields {
+            lo: u9::new(7),
+            mid: true,
+            hi: u22::new(11),
+        }
+    );
+}
+
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(size = 64, derive(Atomic))]
+struct Tagged<T> {
+    tag: u16,
+    #[ribbit(size = 48)]
+    value: T,
+}
+
+#[test]
+fn fields_generic() {
+    let tagged = Atomic::<Tagged<Fields>>::new(Tagged {
+        tag: 1,
+        value: Fields {
+            lo: u9::new(1),
+            mid: true,
+            hi: u22::new(2),
+        },
+    });
+
+    let value = tagged.swap_value(
+        Fields {
+            lo: u9::new(3),
+            mid: false,
+            hi: u22::new(4),
+        }
+        .pack(),
+        Ordering::Relaxed,
+    );
+
+    assert_eq!(value.lo(), u9::new(1));
+    assert_eq!(tagged.load_tag(Ordering::Relaxed), 1);
+    assert_eq!(tagged.load_value(Ordering::Relaxed).hi(), u22::new(4));
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 16, derive(Debug, Eq, Atomic))]
+struct Flags {
+    a: bool,
+    #[ribbit(offset = 9)]
+    b: u1,
+    c: u6,
+}
+
+#[test]
+fn flags() {
+    let flags = Atomic::<Flags>::new(Flags {
+        a: false,
+        b: u1::new(0),
+        c: u6::new(0b10_1010),
+    });
+
+    assert!(!flags.set_a(Ordering::Relaxed).a());
+    assert!(flags.set_a(Ordering::Relaxed).a());
+    assert_eq!(flags.toggle_b(Ordering::Relaxed).b(), u1::new(0));
+    assert_eq!(
+        flags.load(Ordering::Relaxed),
+        Flags {
+            a: true,
+            b: u1::new(1),
+            c: u6::new(0b10_1010),
+        }
+    );
+
+    assert!(flags.clear_a(Ordering::Relaxed).a());
+    assert_eq!(flags.toggle_b(Ordering::Relaxed).b(), u1::new(1));
+    assert_eq!(flags.clear_b(Ordering::Relaxed).b(), u1::new(0));
+    assert_eq!(
+        flags.load(Ordering::Relaxed),
+        Flags {
+            a: false,
+            b: u1::new(0),
+            c: u6::new(0b10_1010),
+        }
+    );
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 32, derive(Debug, Eq, Atomic))]
+struct Counters {
+    sequence: u9,
+    delta: i7,
+    references: u16,
+}
+
+#[test]
+fn counters() {
+    let counters = Atomic::<Counters>::new(Counters {
+        sequence: u9::MAX,
+        delta: i7::new(1),
+        references: u16::MAX,
+    });
+
+    // Most significant field (native)
+    assert_eq!(
+        counters.fetch_add_references(2, Ordering::Relaxed),
+        u16::MAX
+    );
+    assert_eq!(counters.fetch_sub_references(3, Ordering::Relaxed), 1);
+
+    // Other fields (compare-and-swap)
+    assert_eq!(
+        counters.fetch_add_sequence(u9::new(1), Ordering::Relaxed),
+        u9::MAX
+    );
+    assert_eq!(
+        counters.fetch_add_delta(i7::new(3), Ordering::Relaxed),
+        i7::new(1)
+    );
+    assert_eq!(
+        counters.fetch_sub_delta(i7::new(2), Ordering::Relaxed),
+        i7::new(4)
+    );
+
+    assert_eq!(
+        counters.load(Ordering::Relaxed),
+        Counters {
+            sequence: u9::new(0),
+            delta: i7::new(2),
+            references: u16::MAX - 1,
+        }
+    );
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 16, derive(Debug, Eq, Atomic))]
+struct Signed {
+    flag: bool,
+    #[ribbit(offset = 4)]
+    value: i12,
+}
+
+#[test]
+fn signed_native() {
+    let signed = Atomic::<Signed>::new(Signed {
+        flag: true,
+        value: i12::new(5),
+    });
+
+    assert_eq!(
+        signed.fetch_add_value(i12::new(2), Ordering::Relaxed),
+        i12::new(5)
+    );
+    assert_eq!(
+        signed.fetch_sub_value(i12::new(3), Ordering::Relaxed),
+        i12::new(7)
+    );
+    assert_eq!(
+        signed.load(Ordering::Relaxed),
+        Signed {
+            flag: true,
+            value: i12::new(4),
+        }
+    );
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 16, derive(Debug, Eq, Atomic))]
+struct Guarded {
+    count: u11,
+    flag: bool,
+    #[ribbit(guard)]
+    overflows: u4,
+}
+
+#[test]
+fn guarded() {
+    let guarded = Atomic::<Guarded>::new(Guarded {
+        count: u11::MAX,
+        flag: true,
+        overflows: u4::MAX,
+    });
+
+    // Guard field is not directly above (compare-and-swap)
+    assert_eq!(
+        guarded.fetch_add_count(u11::new(2), Ordering::Relaxed),
+        u11::MAX
+    );
+    assert_eq!(
+        guarded.load(Ordering::Relaxed),
+        Guarded {
+            count: u11::new(1),
+            flag: true,
+            overflows: u4::MAX,
+        }
+    );
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 16, derive(Debug, Eq, Atomic))]
+struct Epoch {
+    count: u12,
+    #[ribbit(guard)]
+    overflows: u4,
+}
+
+#[test]
+fn epoch() {
+    let epoch = Atomic::<Epoch>::new(Epoch {
+        count: u12::MAX,
+        overflows: u4::MAX,
+    });
+
+    // Carry lands in the guard field (native)
+    assert_eq!(
+        epoch.fetch_add_count(u12::new(2), Ordering::Relaxed),
+        u12::MAX
+    );
+    assert_eq!(
+        epoch.load(Ordering::Relaxed),
+        Epoch {
+            count: u12::new(1),
+            overflows: u4::new(0),
+        }
+    );
+
+    // Borrow also lands in the guard field
+    assert_eq!(
+        epoch.fetch_sub_count(u12::new(2), Ordering::Relaxed),
+        u12::new(1)
+    );
+    assert_eq!(
+        epoch.load(Ordering::Relaxed),
+        Epoch {
+            count: u12::MAX,
+            overflows: u4::MAX,
+        }
+    );
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 24, derive(Debug, Eq, Atomic))]
+struct Padded {
+    low: u8,
+    high: u16,
+}
+
+#[test]
+fn padded() {
+    let padded = Atomic::<Padded>::new(Padded {
+        low: 1,
+        high: u16::MAX,
+    });
+
+    // Unoccupied bits above the field must stay zero (compare-and-swap)
+    assert_eq!(padded.fetch_add_high(1, Ordering::Relaxed), u16::MAX);
+    assert_eq!(
+        padded.load_packed(Ordering::Relaxed).into_raw(),
+        u24::new(0x00_0001)
+    );
+}
@@ -0,0 +1,143 @@
+use ribbit::u12;
+use ribbit::u4;
+use ribbit::Invalid;
+use ribbit::Pack as _;
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 24, derive(Debug, Eq))]
+struct Triple {
+    a: u12,
+    b: u12,
+}
+
+#[test]
+fn exact() {
+    let triple = Triple {
+        a: u12::new(0x321),
+        b: u12::new(0x654),
+    }
+    .pack();
+
+    assert_eq!(triple.to_le_bytes(), [0x21, 0x43, 0x65]);
+    assert_eq!(triple.to_be_bytes(), [0x65, 0x43, 0x21]);
+    assert_eq!(
+        triple.to_ne_bytes(),
+        match cfg!(target_endian = "little") {
+            true => triple.to_le_bytes(),
+            false => triple.to_be_bytes(),
+        }
+    );
+
+    type Packed = ribbit::Packed<Triple>;
+    assert_eq!(Packed::try_from_le_bytes([0x21, 0x43, 0x65]), Ok(triple));
+    assert_eq!(Packed::try_from_be_bytes([0x65, 0x43, 0x21]), Ok(triple));
+    assert_eq!(Packed::try_from_ne_bytes(triple.to_ne_bytes()), Ok(triple));
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 10, derive(Debug, Eq))]
+enum Partial {
+    #[ribbit(size = 4)]
+    A(u4),
+    #[ribbit(size = 8)]
+    B(u8),
+}
+
+#[test]
+fn partial() {
+    type Packed = ribbit::Packed<Partial>;
+
+    let b = Partial::B(0xff).pack();
+    assert_eq!(b.to_le_bytes(), [0xff, 0x01]);
+    assert_eq!(b.to_be_bytes(), [0x01, 0xff]);
+    assert_eq!(Packed::try_from_be_bytes([0x01, 0xff]), Ok(b));
+
+    // Bits above the size of the packed type
+    let error = Packed::try_from_le_bytes([0xff, 0x05]).unwrap_err();
+    assert_eq!(error.kind(), Invalid::Unused);
+    assert_eq!(error.name(), "Partial");
+    assert_eq!(error.offset(), 10);
+
+    // Unused bits of a variant
+    let error = Packed::try_from_le_bytes([0x00, 0x01]).unwrap_err();
+    assert_eq!(error.kind(), Invalid::Unused);
+    assert_eq!(error.name(), "A");
+    assert_eq!(error.offset(), 8);
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 0, derive(Debug, Eq))]
+struct Empty;
+
+#[test]
+fn empty() {
+    assert_eq!(Empty.pack().to_le_bytes(), []);
+    assert_eq!(
+        ribbit::Packed::<Empty>::try_from_be_bytes([]),
+        Ok(Empty.pack())
+    );
+}
+
+const BYTES: [u8; 3] = ribbit::Packed::<Triple>::new(u12::new(1), u12::new(2)).to_be_bytes();
+
+#[test]
+fn r#const() {
+    assert_eq!(BYTES, [0x00, 0x20, 0x01]);
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 16, bytes(rename = "octets"), derive(Debug, Eq))]
+struct Renamed {
+    a: u8,
+    b: u8,
+}
+
+#[test]
+fn rename() {
+    type Packed = ribbit::Packed<Renamed>;
+
+    let renamed = Renamed { a: 1, b: 2 }.pack();
+    assert_eq!(renamed.to_le_octets(), [1, 2]);
+    assert_eq!(renamed.to_be_octets(), [2, 1]);
+    assert_eq!(Packed::try_from_le_octets([1, 2]), Ok(renamed));
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 16, try_from_raw(skip), derive(Debug, Eq))]
+struct Unchecked {
+    a: u8,
+    b: u8,
+}
+
+// Not generated when `try_from_raw` is skipped
+impl UncheckedPacked {
+    fn try_from_le_bytes(bytes: [u8; 2]) -> Self {
+        Unchecked {
+            a: bytes[0],
+            b: bytes[1],
+        }
+        .pack()
+    }
+}
+
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(size = 16, bytes(skip))]
+struct Skipped {
+    a: u8,
+    b: u8,
+}
+
+// Not generated when `bytes` is skipped
+impl SkippedPacked {
+    fn to_le_bytes(self) -> u16 {
+        self.into_raw()
+    }
+}
+
+#[test]
+fn skip() {
+    let unchecked = ribbit::Packed::<Unchecked>::try_from_le_bytes([1, 2]);
+    assert_eq!(unchecked.to_le_bytes(), [1, 2]);
+
+    assert_eq!(Skipped { a: 1, b: 2 }.pack().to_le_bytes(), 0x0201);
+}
@@ -0,0 +1,84 @@
+#![cfg(feature = "checked")]
+
+use ribbit::u4;
+use ribbit::Pack as _;
+use ribbit::Unpack as _;
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 8, derive(Debug, Eq))]
+enum Kind {
+    A,
+    #[ribbit(size = 4)]
+    B(u4),
+    C,
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 16, derive(Debug, Eq))]
+struct Header {
+    #[ribbit(size = 8)]
+    kind: crate::Kind,
+    count: u8,
+}
+
+#[test]
+fn valid() {
+    let header = Header {
+        kind: Kind::B(u4::new(3)),
+        count: 1,
+    };
+
+    let raw = header.pack().into_raw();
+    assert_eq!(
+        unsafe { ribbit::Packed::<Header>::from_raw_unchecked(raw) }.unpack(),
+        header
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "Invalid bits for `Kind` (raw value 0x3): unknown discriminant in `Kind` at offset 0"
+)]
+fn from_raw_unchecked() {
+    let _ = unsafe { ribbit::Packed::<Kind>::from_raw_unchecked(3) };
+}
+
+#[test]
+#[should_panic(
+    expected = "Invalid bits for `Header` (raw value 0x181): unused bits set in `B` at offset 7"
+)]
+fn unpack_trait() {
+    let _ = unsafe { <ribbit::Packed<Header> as ribbit::Unpack>::from_raw_unchecked(0x181) };
+}
+
+#[test]
+#[should_panic(
+    expected = "Invalid bits for `Kind` (raw value 0x81): unused bits set in `B` at offset 7"
+)]
+fn loose_to_packed() {
+    let _ = unsafe { ribbit::convert::loose_to_packed::<ribbit::Packed<Kind>>(0x81) };
+}
+
+#[test]
+#[should_panic(expected = "Invalid bits for `NonZeroU8` (raw value 0x0)")]
+fn non_zero() {
+    let _ = unsafe { ribbit::convert::loose_to_packed::<core::num::NonZeroU8>(0) };
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug)]
+#[ribbit(size = 136)]
+struct Wide {
+    low: u64,
+    high: u64,
+    #[ribbit(size = 8)]
+    kind: crate::Kind,
+}
+
+#[test]
+#[should_panic(
+    expected = "Invalid bits for `Wide` (raw value 0x300000000000000000000000000000000): unknown discriminant in `Kind` at offset 128"
+)]
+fn wide() {
+    let raw = ribbit::Words::new([0, 0, 3]);
+    let _ = unsafe { ribbit::Packed::<Wide>::from_raw_unchecked(raw) };
+}
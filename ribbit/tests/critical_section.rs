@@ -0,0 +1,59 @@
+#![cfg(feature = "critical-section")]
+
+use core::sync::atomic::Ordering;
+
+use ribbit::atomic::critical_section::AtomicU32;
+use ribbit::u22;
+use ribbit::u9;
+use ribbit::Atomic;
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 32, derive(Debug, Eq, Atomic))]
+struct Counter {
+    flag: bool,
+    lo: u9,
+    hi: u22,
+}
+
+static COUNTER: Atomic<Counter, AtomicU32> = Atomic::<Counter, AtomicU32>::new_packed_const(
+    ribbit::Packed::<Counter>::new(false, u9::new(0), u22::new(0)),
+);
+
+#[test]
+fn contended() {
+    std::thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..100 {
+                    COUNTER.update(Ordering::AcqRel, Ordering::Acquire, |old| Counter {
+                        lo: u9::new((old.lo.value() + 1) % 512),
+                        ..old
+                    });
+                    COUNTER.fetch_add_hi(u22::new(1), Ordering::Relaxed);
+                    COUNTER.toggle_flag(Ordering::Relaxed);
+                }
+            });
+        }
+    });
+
+    assert_eq!(
+        COUNTER.load(Ordering::Relaxed),
+        Counter {
+            flag: false,
+            lo: u9::new(400),
+            hi: u22::new(400),
+        }
+    );
+}
+
+#[test]
+fn additive() {
+    // Enabling the feature does not change the default backend
+    let atomic: Atomic<Counter> = Atomic::<Counter, core::sync::atomic::AtomicU32>::new(Counter {
+        flag: true,
+        lo: u9::new(1),
+        hi: u22::new(2),
+    });
+
+    assert!(atomic.load_flag(Ordering::Relaxed));
+}
@@ -0,0 +1,117 @@
+use ribbit::u12;
+use ribbit::u4;
+use ribbit::Either;
+use ribbit::Pack as _;
+use ribbit::Unpack as _;
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 8, derive(Debug, Eq))]
+struct Small {
+    lo: u4,
+    hi: u4,
+}
+
+#[test]
+fn result() {
+    type Index = Result<u12, u8>;
+
+    assert_eq!(<ribbit::Packed<Index> as ribbit::Unpack>::BITS, 13);
+
+    let ok = Index::Ok(u12::new(0xfff)).pack();
+    assert!(ok.is_ok());
+    assert_eq!(ok.ok(), Some(u12::new(0xfff)));
+    assert_eq!(ok.err(), None);
+    assert_eq!(ok.into_raw(), 0xfff << 1);
+    assert_eq!(ok.unpack(), Ok(u12::new(0xfff)));
+
+    let err = Index::Err(0xff).pack();
+    assert!(err.is_err());
+    assert_eq!(err.err(), Some(0xff));
+    assert_eq!(err.into_raw(), 0xff << 1 | 1);
+    assert_eq!(err.unpack(), Err(0xff));
+}
+
+#[test]
+fn either() {
+    type Value = Either<crate::Small, ()>;
+
+    assert_eq!(<ribbit::Packed<Value> as ribbit::Unpack>::BITS, 9);
+
+    let small = Small {
+        lo: u4::new(1),
+        hi: u4::new(2),
+    };
+
+    let left = Value::Left(small).pack();
+    assert!(left.is_left());
+    assert_eq!(left.left(), Some(small.pack()));
+    assert_eq!(left.unpack(), Either::Left(small));
+
+    let right = Value::Right(()).pack();
+    assert!(right.is_right());
+    assert_eq!(right.unpack(), Either::Right(()));
+}
+
+#[test]
+fn loose() {
+    // Smallest native integer type that fits the tag and the larger payload
+    assert_eq!(size_of::<ribbit::Packed<Result<u4, u4>>>(), 1);
+    assert_eq!(size_of::<ribbit::Packed<Result<u4, u8>>>(), 2);
+    assert_eq!(size_of::<ribbit::Packed<Result<u12, u4>>>(), 2);
+    assert_eq!(size_of::<ribbit::Packed<Either<Small, ()>>>(), 2);
+
+    // Nested payloads can be wider than necessary, since the spare bits of
+    // their loose type are not known at the type level
+    let nested = Either::<Result<u4, u4>, u4>::Left(Err(u4::new(3))).pack();
+    assert_eq!(nested.into_raw(), 0b1110);
+    assert_eq!(nested.unpack(), Either::Left(Err(u4::new(3))));
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 24, derive(Debug, Eq))]
+struct Field {
+    #[ribbit(size = 13)]
+    index: Result<ribbit::u12, u8>,
+    #[ribbit(size = 9)]
+    value: ribbit::Either<crate::Small, u8>,
+    #[ribbit(size = 2)]
+    nested: ribbit::Either<Result<(), ()>, ()>,
+}
+
+#[test]
+fn field() {
+    let field = Field {
+        index: Err(3),
+        value: Either::Right(0xff),
+        nested: Either::Left(Err(())),
+    }
+    .pack();
+
+    assert_eq!(field.index().unpack(), Err(3));
+    assert_eq!(field.value().right(), Some(0xff));
+
+    let field = field.with_index(Result::<_, u8>::Ok(u12::new(5)).pack());
+    assert_eq!(
+        field.unpack(),
+        Field {
+            index: Ok(u12::new(5)),
+            value: Either::Right(0xff),
+            nested: Either::Left(Err(())),
+        }
+    );
+}
+
+#[cfg(all(feature = "atomic", not(all(loom, feature = "loom"))))]
+#[test]
+fn atomic() {
+    use core::sync::atomic::Ordering;
+
+    let atomic = ribbit::Atomic::<Result<u12, u8>>::new(Err(0));
+    assert_eq!(atomic.load(Ordering::Relaxed), Err(0));
+
+    atomic.store(Ok(u12::new(7)), Ordering::Relaxed);
+    assert_eq!(atomic.load(Ordering::Relaxed), Ok(u12::new(7)));
+
+    // Backed by the loose type that fits `BITS`
+    assert_eq!(size_of::<ribbit::Atomic<Result<u4, u4>>>(), 1);
+}
@@ -227,3 +227,485 @@ fn unit_discriminant() {
         UnitDiscriminant::C as u8
     );
 }
+
+const OPCODE: isize = 2;
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 8, derive(Debug, Eq))]
+enum UnitExpression {
+    Load = OPCODE,
+    Store = 1 << 3,
+    Nop,
+    Negative = -1,
+}
+
+#[test]
+fn unit_expression() {
+    for (unpacked, raw) in [
+        (UnitExpression::Load, 2),
+        (UnitExpression::Store, 8),
+        (UnitExpression::Nop, 9),
+        (UnitExpression::Negative, 0xFF),
+    ] {
+        let packed = unpacked.pack();
+        assert_eq!(packed.into_raw(), raw);
+        assert_eq!(packed.unpack(), unpacked);
+    }
+}
+
+const TAG: u8 = 5;
+
+#[repr(u8)]
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 12, derive(Debug, Eq))]
+enum DataExpression {
+    #[ribbit(size = 8)]
+    A(u8) = TAG,
+    #[ribbit(size = 4)]
+    B { b: ribbit::u4 } = TAG + 1,
+}
+
+#[test]
+fn data_expression() {
+    let a = ribbit::Packed::<DataExpression>::new_a(0xAB);
+    assert_eq!(a.into_raw().value(), 0xAB5);
+    assert_eq!(a.unpack(), DataExpression::A(0xAB));
+
+    let b = DataExpression::B {
+        b: ribbit::u4::new(3),
+    }
+    .pack();
+    assert_eq!(b.into_raw().value(), 0x36);
+    assert_eq!(
+        b.unpack(),
+        DataExpression::B {
+            b: ribbit::u4::new(3)
+        }
+    );
+}
+
+// Name of the discriminant table previously generated in the caller's module
+#[allow(dead_code)]
+const _RIBBIT_DISCRIMINANTS_HTTP_CODE: [i128; 1] = [0];
+
+const OK: isize = 200;
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 8, derive(Debug, Eq))]
+enum HttpCode {
+    Ok = OK,
+    NotFound = OK - 196,
+}
+
+#[test]
+fn expression_scope() {
+    for (unpacked, raw) in [(HttpCode::Ok, 200), (HttpCode::NotFound, 4)] {
+        let packed = unpacked.pack();
+        assert_eq!(packed.into_raw(), raw);
+        assert_eq!(packed.unpack(), unpacked);
+    }
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 16, discriminant(size = 4), derive(Debug, Eq))]
+enum DiscriminantSize {
+    #[ribbit(size = 8)]
+    A(u8),
+    #[ribbit(size = 12)]
+    B(ribbit::u12),
+}
+
+#[test]
+fn discriminant_size() {
+    let a = DiscriminantSize::A(0xAB).pack();
+    assert_eq!(a.into_raw(), 0x0AB0);
+    assert_eq!(a.unpack(), DiscriminantSize::A(0xAB));
+
+    let b = DiscriminantSize::B(ribbit::u12::new(0xFFF)).pack();
+    assert_eq!(b.into_raw(), 0xFFF1);
+    assert_eq!(b.unpack(), DiscriminantSize::B(ribbit::u12::new(0xFFF)));
+}
+
+#[repr(u8)]
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 16, discriminant(size = 2, offset = 14), derive(Debug, Eq))]
+enum DiscriminantOffset {
+    #[ribbit(size = 14)]
+    A {
+        a: ribbit::u14,
+    },
+    B,
+    #[ribbit(size = 8)]
+    C(u8) = 3,
+}
+
+#[test]
+fn discriminant_offset() {
+    let a = DiscriminantOffset::A {
+        a: ribbit::u14::new(0x3FFF),
+    }
+    .pack();
+    assert_eq!(a.into_raw(), 0x3FFF);
+    assert_eq!(
+        a.unpack(),
+        DiscriminantOffset::A {
+            a: ribbit::u14::new(0x3FFF)
+        }
+    );
+
+    let b = ribbit::Packed::<DiscriminantOffset>::new_b();
+    assert_eq!(b.into_raw(), 0x4000);
+    assert_eq!(b.unpack(), DiscriminantOffset::B);
+
+    let c = DiscriminantOffset::C(0xFF).pack();
+    assert_eq!(c.into_raw(), 0xC0FF);
+    assert_eq!(c.unpack(), DiscriminantOffset::C(0xFF));
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 64, discriminant = "prefix", derive(Debug, Eq))]
+enum Prefix {
+    #[ribbit(size = 8)]
+    B(u8),
+    #[ribbit(size = 63)]
+    A(ribbit::u63),
+    C,
+    #[ribbit(size = 16)]
+    D {
+        d: u16,
+    },
+}
+
+#[test]
+fn prefix() {
+    let a = Prefix::A(ribbit::u63::new((1 << 63) - 1)).pack();
+    assert_eq!(a.into_raw(), u64::MAX << 1);
+    assert_eq!(a.unpack(), Prefix::A(ribbit::u63::new((1 << 63) - 1)));
+
+    let b = Prefix::B(0xAB).pack();
+    assert_eq!(b.into_raw(), 0xAB << 3 | 0b001);
+    assert_eq!(b.unpack(), Prefix::B(0xAB));
+
+    let c = ribbit::Packed::<Prefix>::new_c();
+    assert_eq!(c.into_raw(), 0b101);
+    assert_eq!(c.unpack(), Prefix::C);
+
+    let d = Prefix::D { d: 0xFFFF }.pack();
+    assert_eq!(d.into_raw(), 0xFFFF << 3 | 0b011);
+    assert_eq!(d.unpack(), Prefix::D { d: 0xFFFF });
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+// Reserve discriminants for future variants
+#[ribbit(size = 6, discriminant(size = 2), derive(Debug, Eq))]
+enum NonExhaustive {
+    A,
+    #[ribbit(size = 6, non_exhaustive)]
+    Unknown(ribbit::u6),
+    #[ribbit(size = 4)]
+    B(ribbit::u4),
+}
+
+#[test]
+fn non_exhaustive() {
+    let a = NonExhaustive::A.pack();
+    assert_eq!(a.into_raw().value(), 0b00);
+    assert_eq!(a.unpack(), NonExhaustive::A);
+
+    let b = NonExhaustive::B(ribbit::u4::new(0xF)).pack();
+    assert_eq!(b.into_raw().value(), 0b111101);
+    assert_eq!(b.unpack(), NonExhaustive::B(ribbit::u4::new(0xF)));
+
+    // Written by a newer version with more variants
+    let c =
+        unsafe { ribbit::Packed::<NonExhaustive>::from_raw_unchecked(ribbit::u6::new(0b101110)) };
+    assert_eq!(
+        c.unpack(),
+        NonExhaustive::Unknown(ribbit::u6::new(0b101110))
+    );
+    assert_eq!(c.unpack().pack(), c);
+    assert_eq!(format!("{c:?}"), "Unknown(46)");
+}
+
+#[test]
+#[should_panic(
+    expected = "Raw value of `NonExhaustive::Unknown` has the discriminant of a known variant"
+)]
+fn non_exhaustive_known() {
+    // Discriminant of `B`
+    let _ = NonExhaustive::Unknown(ribbit::u6::new(0b101101)).pack();
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 4, derive(Debug, Eq))]
+enum Inner {
+    A,
+    B,
+    C,
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 5, discriminant(size = 1), derive(Debug, Eq))]
+enum Outer {
+    #[ribbit(size = 4)]
+    X(crate::Inner),
+    #[ribbit(size = 5, non_exhaustive)]
+    Unknown(ribbit::u5),
+}
+
+#[test]
+fn non_exhaustive_nested() {
+    let x = Outer::X(Inner::C).pack();
+    assert_eq!(x.into_raw().value(), 0b00100);
+    assert_eq!(x.unpack(), Outer::X(Inner::C));
+
+    // Payload is not a valid `Inner`, but the discriminant is unknown
+    let unknown = Outer::Unknown(ribbit::u5::new(0b00111)).pack();
+    assert_eq!(unknown.into_raw().value(), 0b00111);
+    assert_eq!(unknown.unpack(), Outer::Unknown(ribbit::u5::new(0b00111)));
+}
+
+#[test]
+#[should_panic(expected = "Raw value of `Outer::Unknown` has the discriminant of a known variant")]
+fn non_exhaustive_nested_known() {
+    // Discriminant of `X` with an invalid `Inner`
+    let _ = Outer::Unknown(ribbit::u5::new(0b00110)).pack();
+}
+
+#[test]
+#[should_panic(expected = "Raw value of `Outer::Unknown` has the discriminant of a known variant")]
+fn non_exhaustive_nested_zero() {
+    let _ = Outer::Unknown(ribbit::u5::new(0)).pack();
+}
+
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(size = 18)]
+enum Variant {
+    Unit,
+    #[ribbit(size = 16)]
+    Tuple(u8, u8),
+    #[ribbit(size = 9)]
+    Struct {
+        a: bool,
+        #[ribbit(size = 8)]
+        b: crate::Byte,
+    },
+}
+
+#[test]
+fn variant() {
+    type Packed = ribbit::Packed<Variant>;
+
+    assert_eq!(Packed::VARIANT_COUNT, 3);
+    assert_eq!(Packed::DISCRIMINANTS, [0, 1, 2]);
+
+    let unit = const { Packed::new_unit() };
+    assert!(unit.is_unit());
+    assert!(!unit.is_tuple());
+    assert_eq!(unit.discriminant(), 0);
+    assert!(unit.tuple().is_none());
+
+    let tuple = const { Packed::new_tuple(3, 5) };
+    assert!(tuple.is_tuple());
+    assert_eq!(tuple.discriminant(), 1);
+    let view = const { Packed::new_tuple(3, 5).tuple().unwrap() };
+    assert_eq!((view._0(), view._1()), (3, 5));
+    assert_eq!(view.into_raw(), 0x0503);
+
+    let r#struct = Variant::Struct {
+        a: true,
+        b: Byte(7),
+    }
+    .pack();
+    assert!(r#struct.is_struct());
+    assert!(r#struct.tuple().is_none());
+    let view = r#struct.r#struct().unwrap();
+    assert!(view.a());
+    assert_eq!(view.b().unpack().0, 7);
+}
+
+#[test]
+fn variant_prefix() {
+    type Packed = ribbit::Packed<Prefix>;
+
+    assert_eq!(Packed::VARIANT_COUNT, 4);
+    assert_eq!(Packed::DISCRIMINANTS, [0b001, 0b0, 0b101, 0b011]);
+
+    let a = Prefix::A(ribbit::u63::new(5)).pack();
+    assert!(a.is_a() && !a.is_b());
+    assert_eq!(a.discriminant(), 0);
+    assert_eq!(a.a().unwrap()._0().value(), 5);
+
+    let d = Prefix::D { d: 0xFFFF }.pack();
+    assert!(d.is_d() && !d.is_a());
+    assert_eq!(d.discriminant(), 0b011);
+    assert_eq!(d.d().unwrap().d(), 0xFFFF);
+}
+
+#[test]
+fn variant_non_exhaustive() {
+    type Packed = ribbit::Packed<NonExhaustive>;
+
+    assert_eq!(Packed::VARIANT_COUNT, 3);
+    assert_eq!(Packed::DISCRIMINANTS, [0, 1]);
+
+    let a = NonExhaustive::A.pack();
+    assert!(a.is_a() && !a.is_unknown());
+    assert!(a.unknown().is_none());
+
+    let unknown = unsafe { Packed::from_raw_unchecked(ribbit::u6::new(0b101110)) };
+    assert!(unknown.is_unknown() && !unknown.is_a() && !unknown.is_b());
+    assert_eq!(unknown.discriminant(), 0b10);
+    assert_eq!(unknown.unknown().unwrap()._0().value(), 0b101110);
+}
+
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(
+    size = 9,
+    variants(
+        discriminant(rename = "tag"),
+        variant_count(skip),
+        discriminants(rename = "TAGS"),
+    )
+)]
+enum Op {
+    #[ribbit(size = 8)]
+    Discriminant(u8),
+    #[ribbit(size = 8, get(rename = "as_into_raw"), is(skip))]
+    IntoRaw(u8),
+}
+
+#[test]
+fn variant_rename() {
+    type Packed = ribbit::Packed<Op>;
+
+    assert_eq!(Packed::TAGS, [0, 1]);
+
+    let op = Op::Discriminant(3).pack();
+    assert_eq!(op.tag(), 0);
+    assert!(op.is_discriminant());
+    assert_eq!(op.discriminant().unwrap()._0(), 3);
+    assert!(op.as_into_raw().is_none());
+    assert_eq!(op.into_raw().value(), 3 << 1);
+
+    let op = Op::IntoRaw(5).pack();
+    assert_eq!(op.tag(), 1);
+    assert_eq!(op.as_into_raw().unwrap()._0(), 5);
+    assert_eq!(op.into_raw_0(), Some(5));
+}
+
+#[test]
+fn variant_field() {
+    type Packed = ribbit::Packed<Variant>;
+
+    let tuple = const { Packed::new_tuple(3, 5).with_tuple_1(7) }.unwrap();
+    assert_eq!((tuple.tuple_0(), tuple.tuple_1()), (Some(3), Some(7)));
+    assert_eq!(tuple.struct_a(), None);
+    assert!(tuple.with_struct_a(true).is_none());
+
+    let r#struct = Packed::new_struct(false, Byte(1).pack())
+        .with_struct_a(true)
+        .and_then(|r#struct| r#struct.with_struct_b(Byte(9).pack()))
+        .unwrap();
+    assert_eq!(r#struct.struct_a(), Some(true));
+    assert_eq!(r#struct.struct_b().map(|b| b.unpack().0), Some(9));
+    assert_eq!(r#struct.tuple_0(), None);
+}
+
+#[test]
+fn variant_field_offset() {
+    let c = DiscriminantOffset::C(0x0F).pack();
+    assert_eq!(c.c_0(), Some(0x0F));
+    assert_eq!(c.a_a(), None);
+
+    let c = c.with_c_0(0xF0).unwrap();
+    assert_eq!(c.into_raw(), 0xC0F0);
+    assert_eq!(c.unpack(), DiscriminantOffset::C(0xF0));
+
+    let a = DiscriminantOffset::A {
+        a: ribbit::u14::new(0),
+    }
+    .pack()
+    .with_a_a(ribbit::u14::new(0x3FFF))
+    .unwrap();
+    assert_eq!(a.into_raw(), 0x3FFF);
+    assert_eq!(a.a_a(), Some(ribbit::u14::new(0x3FFF)));
+}
+
+#[test]
+fn view() {
+    type Packed = ribbit::Packed<Variant>;
+
+    let sum = |packed: Packed| match packed.view() {
+        VariantView::Unit => 0,
+        VariantView::Tuple(tuple) => tuple._0() + tuple._1(),
+        // Nested packed types stay packed
+        VariantView::Struct(r#struct) => r#struct.b().unpack().0 + r#struct.a() as u8,
+    };
+
+    assert_eq!(sum(Packed::new_unit()), 0);
+    assert_eq!(sum(Packed::new_tuple(3, 5)), 8);
+    assert_eq!(sum(Packed::new_struct(true, Byte(9).pack())), 10);
+
+    const TUPLE: u8 = match Packed::new_tuple(3, 5).view() {
+        VariantView::Tuple(tuple) => tuple._1(),
+        _ => unreachable!(),
+    };
+    assert_eq!(TUPLE, 5);
+}
+
+#[test]
+fn view_eq() {
+    type Packed = ribbit::Packed<Variant>;
+
+    let tuple = Packed::new_tuple(3, 5);
+    assert_eq!(tuple.view(), tuple.view());
+    assert_ne!(tuple.view(), Packed::new_tuple(3, 6).view());
+    assert_ne!(tuple.view(), Packed::new_unit().view());
+    assert_eq!(Packed::new_unit().view(), VariantView::Unit);
+
+    // Payload views print their raw value, so fields don't need `Debug`
+    assert_eq!(format!("{:?}", Packed::new_unit().view()), "Unit");
+    assert_eq!(
+        format!("{:?}", tuple.view()),
+        "Tuple(VariantPackedTuple(1283))"
+    );
+    assert_eq!(
+        format!("{:?}", Packed::new_struct(true, Byte(9).pack()).view()),
+        "Struct(VariantPackedStruct(19))"
+    );
+}
+
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(size = 9, view(skip))]
+enum Mode {
+    #[ribbit(size = 8)]
+    View(u8),
+    Edit,
+}
+
+// Not generated with `view(skip)`
+#[allow(dead_code)]
+enum ModeView {}
+
+#[test]
+fn view_skip() {
+    let view = Mode::View(7).pack();
+    assert_eq!(view.view().unwrap()._0(), 7);
+    assert!(Mode::Edit.pack().view().is_none());
+}
+
+#[test]
+fn view_non_exhaustive() {
+    let unknown =
+        unsafe { ribbit::Packed::<NonExhaustive>::from_raw_unchecked(ribbit::u6::new(0b101110)) };
+    match unknown.view() {
+        NonExhaustiveView::Unknown(unknown) => assert_eq!(unknown._0().value(), 0b101110),
+        _ => unreachable!(),
+    }
+
+    match NonExhaustive::B(ribbit::u4::new(3)).pack().view() {
+        NonExhaustiveView::B(b) => assert_eq!(b._0().value(), 3),
+        _ => unreachable!(),
+    }
+}
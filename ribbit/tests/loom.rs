@@ -0,0 +1,207 @@
+#![cfg(all(loom, feature = "loom"))]
+
+use loom::sync::atomic::Ordering;
+use loom::sync::Arc;
+use loom::thread;
+
+use ribbit::u22;
+use ribbit::u26;
+use ribbit::u9;
+use ribbit::Atomic;
+use ribbit::Pack as _;
+use ribbit::Unpack as _;
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 32, derive(Debug, Eq))]
+struct Aligned {
+    lo: u16,
+    hi: u16,
+}
+
+#[test]
+fn aligned() {
+    loom::model(|| {
+        let aligned = Arc::new(Atomic::<Aligned>::new(Aligned { lo: 5, hi: 10 }));
+
+        let threads = (0..2)
+            .map(|i| {
+                let aligned = aligned.clone();
+                thread::spawn(move || {
+                    aligned
+                        .compare_exchange(
+                            Aligned { lo: 5, hi: 10 },
+                            Aligned { lo: 6 + i, hi: 11 },
+                            Ordering::AcqRel,
+                            Ordering::Acquire,
+                        )
+                        .is_ok()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let succeeded = threads
+            .into_iter()
+            .map(|thread| thread.join().unwrap())
+            .filter(|succeeded| *succeeded)
+            .count();
+
+        assert_eq!(succeeded, 1);
+        assert_eq!(aligned.load(Ordering::Relaxed).hi, 11);
+    });
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 32, derive(Debug, Eq))]
+struct Unaligned {
+    lo: u9,
+    hi: u22,
+}
+
+#[test]
+fn unaligned() {
+    loom::model(|| {
+        let unaligned = Arc::new(Atomic::<Unaligned>::new(Unaligned {
+            lo: u9::new(5),
+            hi: u22::new(10),
+        }));
+
+        let writer = {
+            let unaligned = unaligned.clone();
+            thread::spawn(move || {
+                unaligned.store(
+                    Unaligned {
+                        lo: u9::new(6),
+                        hi: u22::new(11),
+                    },
+                    Ordering::Release,
+                )
+            })
+        };
+
+        // Readers never observe a torn value
+        let value = unaligned.load(Ordering::Acquire);
+        assert!(
+            value
+                == Unaligned {
+                    lo: u9::new(5),
+                    hi: u22::new(10),
+                }
+                || value
+                    == Unaligned {
+                        lo: u9::new(6),
+                        hi: u22::new(11),
+                    }
+        );
+
+        writer.join().unwrap();
+    });
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 64, derive(Debug, Eq))]
+struct Undersized {
+    lo: u9,
+    hi: u26,
+}
+
+#[test]
+fn undersized() {
+    loom::model(|| {
+        let undersized = Arc::new(Atomic::<Undersized>::new(Undersized {
+            lo: u9::new(0),
+            hi: u26::new(0),
+        }));
+
+        let threads = (0..2)
+            .map(|_| {
+                let undersized = undersized.clone();
+                thread::spawn(move || {
+                    undersized.update(Ordering::AcqRel, Ordering::Acquire, |old| Undersized {
+                        lo: u9::new(old.lo.value() + 1),
+                        hi: u26::new(old.hi.value() + 2),
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        assert_eq!(
+            undersized.load(Ordering::Relaxed),
+            Undersized {
+                lo: u9::new(2),
+                hi: u26::new(4),
+            }
+        );
+    });
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 64, derive(Debug, Eq))]
+struct Mutable {
+    lo: u9,
+    hi: u26,
+}
+
+#[test]
+fn mutable() {
+    loom::model(|| {
+        let mut mutable = Atomic::<Mutable>::new(Mutable {
+            lo: u9::new(5),
+            hi: u26::new(10),
+        });
+
+        let a = Mutable {
+            lo: u9::new(9),
+            hi: u26::new(3),
+        };
+        mutable.set(a);
+        assert_eq!(mutable.get(), a);
+        assert_eq!(mutable.load(Ordering::Relaxed).pack(), a.pack());
+        assert_eq!(mutable.load_packed(Ordering::Relaxed).unpack(), a);
+    });
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 32, derive(Debug, Eq, Atomic))]
+struct Fields {
+    flag: bool,
+    #[ribbit(offset = 16)]
+    count: u16,
+}
+
+#[test]
+fn fields() {
+    loom::model(|| {
+        let fields = Arc::new(Atomic::<Fields>::new(Fields {
+            flag: false,
+            count: 0,
+        }));
+
+        let writer = {
+            let fields = fields.clone();
+            thread::spawn(move || {
+                fields.fetch_add_count(1, Ordering::Relaxed);
+                fields.set_flag(Ordering::Release);
+            })
+        };
+
+        fields.fetch_add_count(1, Ordering::Relaxed);
+
+        if fields.load_flag(Ordering::Acquire) {
+            assert!(fields.load_count(Ordering::Relaxed) >= 1);
+        }
+
+        writer.join().unwrap();
+
+        assert_eq!(
+            fields.load(Ordering::Relaxed),
+            Fields {
+                flag: true,
+                count: 2,
+            }
+        );
+    });
+}
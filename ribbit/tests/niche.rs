@@ -0,0 +1,132 @@
+use ribbit::u4;
+use ribbit::u5;
+use ribbit::u6;
+use ribbit::u7;
+use ribbit::Niche;
+use ribbit::Pack as _;
+use ribbit::Unpack as _;
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 8, derive(Debug, Eq))]
+enum Fixed {
+    A,
+    #[ribbit(size = 4)]
+    B(u4),
+    #[ribbit(size = 6)]
+    C {
+        c: u6,
+    },
+}
+
+#[test]
+fn fixed() {
+    assert_eq!(
+        core::mem::size_of::<ribbit::Packed<Fixed>>(),
+        core::mem::size_of::<ribbit::Packed<Niche<Fixed>>>(),
+    );
+
+    let none = Niche::<Fixed>(None).pack();
+    assert!(none.is_none());
+    assert_eq!(none.into_raw(), 0b11);
+    assert_eq!(none.unpack(), Niche(None));
+
+    for fixed in [Fixed::A, Fixed::B(u4::new(5)), Fixed::C { c: u6::new(63) }] {
+        let some = Niche(Some(fixed)).pack();
+        assert!(some.is_some());
+        assert_eq!(some.get(), Some(fixed.pack()));
+        assert_eq!(some.unpack(), Niche(Some(fixed)));
+    }
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 8, discriminant(size = 2, offset = 6))]
+#[repr(u8)]
+enum Explicit {
+    #[ribbit(size = 6)]
+    A(u6) = 0,
+    B = 1,
+    C = 3,
+}
+
+#[test]
+fn explicit() {
+    let none = Niche::<Explicit>(None).pack();
+    assert_eq!(none.into_raw(), 2 << 6);
+    assert_eq!(none.unpack(), Niche(None));
+
+    let some = Niche(Some(Explicit::A(u6::new(2)))).pack();
+    assert_eq!(some.unpack(), Niche(Some(Explicit::A(u6::new(2)))));
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 8, discriminant = "prefix")]
+enum Prefix {
+    #[ribbit(size = 7)]
+    A(u7),
+    #[ribbit(size = 5)]
+    B(u5),
+    #[ribbit(size = 5)]
+    C(u5),
+    #[ribbit(size = 5)]
+    D(u5),
+}
+
+#[test]
+fn prefix() {
+    let none = Niche::<Prefix>(None).pack();
+    assert_eq!(none.into_raw(), 0b111);
+    assert_eq!(none.unpack(), Niche(None));
+
+    for prefix in [
+        Prefix::A(u7::new(127)),
+        Prefix::B(u5::new(31)),
+        Prefix::C(u5::new(0)),
+        Prefix::D(u5::new(31)),
+    ] {
+        assert_eq!(Niche(Some(prefix)).pack().unpack(), Niche(Some(prefix)));
+    }
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 16)]
+struct Field {
+    #[ribbit(size = 8)]
+    fixed: ribbit::Niche<crate::Fixed>,
+    byte: u8,
+}
+
+#[test]
+fn field() {
+    let field = Field {
+        fixed: Niche(None),
+        byte: 0xff,
+    }
+    .pack();
+
+    assert!(field.fixed().is_none());
+    assert_eq!(field.byte(), 0xff);
+
+    let field = field.with_fixed(Niche(Some(Fixed::B(u4::new(3)))).pack());
+    assert_eq!(field.fixed().unpack(), Niche(Some(Fixed::B(u4::new(3)))));
+    assert_eq!(field.byte(), 0xff);
+}
+
+#[cfg(all(feature = "atomic", not(all(loom, feature = "loom"))))]
+#[test]
+fn atomic() {
+    use core::sync::atomic::Ordering;
+
+    let atomic = ribbit::Atomic::<Niche<Fixed>>::new(Niche(None));
+    assert_eq!(atomic.load(Ordering::Relaxed), Niche(None));
+
+    assert!(atomic
+        .compare_exchange(
+            Niche(None),
+            Niche(Some(Fixed::A)),
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        )
+        .is_ok());
+
+    assert_eq!(atomic.load(Ordering::Relaxed), Niche(Some(Fixed::A)));
+}
@@ -0,0 +1,113 @@
+use ribbit::u12;
+use ribbit::u4;
+use ribbit::Pack as _;
+use ribbit::Unpack as _;
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 8, derive(Debug, Eq))]
+struct Small {
+    lo: u4,
+    hi: u4,
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 31, derive(Debug, Eq))]
+struct Flag {
+    #[ribbit(option = "flag")]
+    count: Option<u12>,
+    #[ribbit(size = 9, option = "flag")]
+    small: Option<crate::Small>,
+    #[ribbit(option = "flag")]
+    byte: Option<u8>,
+}
+
+#[test]
+fn flag() {
+    let none = Flag {
+        count: None,
+        small: None,
+        byte: None,
+    }
+    .pack();
+
+    assert_eq!(none.into_raw().value(), 0);
+    assert_eq!(none.count(), None);
+    assert_eq!(none.small(), None);
+    assert_eq!(none.byte(), None);
+
+    let zero = Flag {
+        count: Some(u12::new(0)),
+        small: Some(Small {
+            lo: u4::new(0),
+            hi: u4::new(0),
+        }),
+        byte: Some(0),
+    }
+    .pack();
+
+    assert_eq!(zero.into_raw().value(), 1 << 12 | 1 << 21 | 1 << 30);
+    assert_eq!(zero.count(), Some(u12::new(0)));
+    assert_eq!(zero.byte(), Some(0));
+    assert_eq!(
+        zero.unpack().small,
+        Some(Small {
+            lo: u4::new(0),
+            hi: u4::new(0)
+        })
+    );
+}
+
+#[test]
+fn flag_with() {
+    let small = Small {
+        lo: u4::new(0xf),
+        hi: u4::new(0x3),
+    };
+
+    let flag = ribbit::Packed::<Flag>::new(None, Some(small.pack()), None)
+        .with_count(Some(u12::new(0xfff)))
+        .with_byte(Some(0xff));
+
+    assert_eq!(
+        flag.unpack(),
+        Flag {
+            count: Some(u12::new(0xfff)),
+            small: Some(small),
+            byte: Some(0xff),
+        }
+    );
+
+    let flag = flag.with_small(None).with_count(None);
+    assert_eq!(flag.count(), None);
+    assert_eq!(flag.small(), None);
+    assert_eq!(flag.byte(), Some(0xff));
+}
+
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(size = 9)]
+struct Generic<T> {
+    #[ribbit(size = 9, option = "flag")]
+    value: Option<T>,
+}
+
+#[test]
+fn flag_generic() {
+    let generic = Generic {
+        value: Some(Small {
+            lo: u4::new(1),
+            hi: u4::new(2),
+        }),
+    }
+    .pack();
+
+    assert_eq!(
+        generic.value().map(|small| small.unpack()),
+        Some(Small {
+            lo: u4::new(1),
+            hi: u4::new(2),
+        })
+    );
+
+    let generic = generic.with_value(None);
+    assert!(generic.value().is_none());
+}
@@ -0,0 +1,106 @@
+use ribbit::NonNullPtr;
+use ribbit::Ptr;
+
+#[repr(align(8))]
+struct Node {
+    value: u64,
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 64, derive(Debug, Eq))]
+struct Head {
+    #[ribbit(size = 45)]
+    next: ribbit::Ptr<crate::Node, 3, 48>,
+    generation: u16,
+}
+
+#[test]
+fn field() {
+    let node = Node { value: 5 };
+    let head = ribbit::Packed::<Head>::new(Ptr::from_ref(&node), 7);
+
+    assert_eq!(head.next().as_ptr().cast_const(), &raw const node);
+    assert_eq!(unsafe { head.next().as_ref() }.unwrap().value, 5);
+    assert_eq!(head.generation(), 7);
+
+    let head = head.with_generation(u16::MAX);
+    assert_eq!(head.next(), Ptr::from_ref(&node));
+
+    let head = head.with_next(Ptr::null());
+    assert!(head.next().is_null());
+    assert_eq!(head.generation(), u16::MAX);
+    assert_eq!(head.into_raw() >> 45, u16::MAX as u64);
+}
+
+#[test]
+fn new() {
+    let node = Node { value: 5 };
+    let address = (&raw const node).cast_mut();
+
+    assert!(Ptr::<Node, 3, 48>::new(address).is_some());
+    assert!(Ptr::<Node, 3, 48>::new(address.wrapping_byte_add(4)).is_none());
+    assert!(Ptr::<Node, 3, 48>::new(core::ptr::without_provenance_mut(1 << 48)).is_none());
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 64, derive(Debug, Eq))]
+struct OptionHead {
+    #[ribbit(size = 45)]
+    next: Option<ribbit::NonNullPtr<crate::Node, 3, 48>>,
+    generation: u16,
+}
+
+#[test]
+fn non_null() {
+    assert_eq!(
+        size_of::<Option<NonNullPtr<Node, 3, 48>>>(),
+        size_of::<NonNullPtr<Node, 3, 48>>(),
+    );
+
+    let node = Node { value: 5 };
+    let head = ribbit::Packed::<OptionHead>::new(None, 1);
+    assert!(head.next().is_none());
+
+    let head = head.with_next(Some(NonNullPtr::from_ref(&node)));
+    assert_eq!(
+        head.next().unwrap().as_non_null().as_ptr().cast_const(),
+        &raw const node
+    );
+    assert_eq!(head.generation(), 1);
+}
+
+#[cfg(feature = "atomic")]
+#[test]
+fn atomic() {
+    use core::sync::atomic::Ordering;
+
+    let a = Node { value: 1 };
+    let b = Node { value: 2 };
+
+    let head = ribbit::Atomic::<Head>::new(Head {
+        next: Ptr::from_ref(&a),
+        generation: 0,
+    });
+
+    let old = head.load(Ordering::Relaxed);
+    head.store(
+        Head {
+            next: Ptr::from_ref(&b),
+            generation: 1,
+        },
+        Ordering::Relaxed,
+    );
+
+    // Same pointer, different generation
+    head.store(
+        Head {
+            next: Ptr::from_ref(&a),
+            generation: 2,
+        },
+        Ordering::Relaxed,
+    );
+
+    assert!(head
+        .compare_exchange(old, old, Ordering::Relaxed, Ordering::Relaxed)
+        .is_err());
+}
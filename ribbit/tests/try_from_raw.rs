@@ -0,0 +1,224 @@
+use core::num::NonZeroU8;
+
+use ribbit::u12;
+use ribbit::u2;
+use ribbit::u4;
+use ribbit::u6;
+use ribbit::Invalid;
+use ribbit::Niche;
+use ribbit::Pack as _;
+use ribbit::Unpack as _;
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 8, derive(Debug, Eq))]
+enum Kind {
+    A,
+    #[ribbit(size = 4)]
+    B(u4),
+    C,
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 32, derive(Debug, Eq))]
+struct Header {
+    #[ribbit(size = 8)]
+    kind: crate::Kind,
+    count: NonZeroU8,
+    #[ribbit(option = "flag")]
+    extra: Option<u4>,
+    // Bits 21..32 are unused
+}
+
+fn header() -> Header {
+    Header {
+        kind: Kind::B(u4::new(0xa)),
+        count: NonZeroU8::new(3).unwrap(),
+        extra: Some(u4::new(5)),
+    }
+}
+
+#[test]
+fn valid() {
+    let header = header().pack();
+    let raw = header.into_raw();
+
+    assert_eq!(ribbit::Packed::<Header>::try_from_raw(raw), Ok(header));
+    assert_eq!(ribbit::Packed::<Header>::try_from(raw), Ok(header));
+
+    let none = Header {
+        extra: None,
+        ..self::header()
+    }
+    .pack();
+    assert_eq!(
+        ribbit::Packed::<Header>::try_from(none.into_raw()),
+        Ok(none)
+    );
+}
+
+#[test]
+fn unused() {
+    let raw = header().pack().into_raw();
+
+    let error = ribbit::Packed::<Header>::try_from(raw | 1 << 24).unwrap_err();
+    assert_eq!(error.kind(), Invalid::Unused);
+    assert_eq!(error.name(), "Header");
+    assert_eq!(error.offset(), 24);
+
+    // Unused bits of a nested variant
+    let error = ribbit::Packed::<Header>::try_from(raw | 1 << 7).unwrap_err();
+    assert_eq!(error.kind(), Invalid::Unused);
+    assert_eq!(error.name(), "B");
+    assert_eq!(error.offset(), 7);
+
+    // Payload of an absent option
+    let error = ribbit::Packed::<Header>::try_from(raw & !(1 << 20)).unwrap_err();
+    assert_eq!(error.kind(), Invalid::Unused);
+    assert_eq!(error.name(), "extra");
+    assert_eq!(error.offset(), 16);
+}
+
+#[test]
+fn discriminant() {
+    let raw = header().pack().into_raw();
+
+    let error = ribbit::Packed::<Header>::try_from(raw | 0b11).unwrap_err();
+    assert_eq!(error.kind(), Invalid::Discriminant);
+    assert_eq!(error.name(), "Kind");
+    assert_eq!(error.offset(), 0);
+
+    assert_eq!(
+        ribbit::Packed::<Kind>::try_from(0b11).unwrap_err().kind(),
+        Invalid::Discriminant
+    );
+}
+
+#[test]
+fn zero() {
+    let raw = header().pack().into_raw();
+
+    let error = ribbit::Packed::<Header>::try_from(raw & !(0xff << 8)).unwrap_err();
+    assert_eq!(error.kind(), Invalid::Zero);
+    assert_eq!(error.name(), "count");
+    assert_eq!(error.offset(), 8);
+    assert_eq!(
+        error.to_string(),
+        "Invalid bits: zero value for non-zero field in `count` at offset 8"
+    );
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 12, derive(Debug, Eq))]
+struct Loose {
+    value: u12,
+}
+
+#[test]
+fn loose() {
+    assert!(ribbit::Packed::<Loose>::try_from(0xfff).is_ok());
+
+    let error = ribbit::Packed::<Loose>::try_from(0x2fff).unwrap_err();
+    assert_eq!(error.kind(), Invalid::Unused);
+    assert_eq!(error.name(), "Loose");
+    assert_eq!(error.offset(), 13);
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 8, discriminant(size = 2, offset = 6), derive(Debug, Eq))]
+enum Offset {
+    #[ribbit(size = 6)]
+    A { a: u6 },
+    #[ribbit(size = 2)]
+    B { b: u2 },
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 16, derive(Debug, Eq))]
+struct Wrapper {
+    #[ribbit(size = 8)]
+    low: u8,
+    #[ribbit(size = 8)]
+    high: crate::Offset,
+}
+
+#[test]
+fn nested_offset() {
+    let wrapper = Wrapper {
+        low: 0xff,
+        high: Offset::B { b: u2::new(3) },
+    }
+    .pack();
+
+    let raw = wrapper.into_raw();
+    assert_eq!(ribbit::Packed::<Wrapper>::try_from(raw), Ok(wrapper));
+
+    let error = ribbit::Packed::<Wrapper>::try_from(raw | 1 << 12).unwrap_err();
+    assert_eq!(error.kind(), Invalid::Unused);
+    assert_eq!(error.name(), "B");
+    assert_eq!(error.offset(), 12);
+
+    let error = ribbit::Packed::<Wrapper>::try_from(raw | 0b11 << 14).unwrap_err();
+    assert_eq!(error.kind(), Invalid::Discriminant);
+    assert_eq!(error.name(), "Offset");
+    assert_eq!(error.offset(), 14);
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 6, discriminant(size = 2), derive(Debug, Eq))]
+enum NonExhaustive {
+    A,
+    #[ribbit(size = 6, non_exhaustive)]
+    Unknown(ribbit::u6),
+}
+
+#[test]
+fn non_exhaustive() {
+    let unknown = ribbit::Packed::<NonExhaustive>::try_from(0b101110).unwrap();
+    assert_eq!(unknown.unpack(), NonExhaustive::Unknown(u6::new(0b101110)));
+
+    let error = ribbit::Packed::<NonExhaustive>::try_from(0b100).unwrap_err();
+    assert_eq!(error.kind(), Invalid::Unused);
+    assert_eq!(error.name(), "A");
+    assert_eq!(error.offset(), 2);
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 17, derive(Debug, Eq))]
+struct Library {
+    #[ribbit(size = 8)]
+    niche: ribbit::Niche<crate::Kind>,
+    #[ribbit(size = 9)]
+    result: Result<ribbit::NonZeroU8, ribbit::u4>,
+}
+
+#[test]
+fn library() {
+    let library = Library {
+        niche: Niche(None),
+        result: Err(u4::new(1)),
+    }
+    .pack();
+
+    let raw = library.into_raw().value();
+    assert_eq!(ribbit::Packed::<Library>::try_from(raw), Ok(library));
+
+    // Payload of `Err` above the tag bit is only 4 of 8 bits
+    let error = ribbit::Packed::<Library>::try_from(raw | 1 << 13).unwrap_err();
+    assert_eq!(error.kind(), Invalid::Unused);
+    assert_eq!(error.name(), "result");
+    assert_eq!(error.offset(), 13);
+
+    // `Ok` payload must be non-zero
+    let error = ribbit::Packed::<Library>::try_from(raw & 0xff).unwrap_err();
+    assert_eq!(error.kind(), Invalid::Zero);
+    assert_eq!(error.name(), "result");
+    assert_eq!(error.offset(), 9);
+}
+
+const HEADER: Result<ribbit::Packed<Header>, ribbit::InvalidBits> =
+    ribbit::Packed::<Header>::try_from_raw(ribbit::u32::MAX);
+
+#[test]
+fn r#const() {
+    assert_eq!(HEADER.unwrap_err().kind(), Invalid::Discriminant);
+}
@@ -0,0 +1,9 @@
+const LEN: usize = 4;
+
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(size = 32)]
+struct Bad {
+    a: [u8; LEN],
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(size = 8)]
+enum Kind {
+    A,
+    B,
+}
+
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(size = 30)]
+struct Bad {
+    #[ribbit(size = 30)]
+    kinds: [crate::Kind; 4],
+}
+
+fn main() {}
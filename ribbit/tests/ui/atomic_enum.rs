@@ -0,0 +1,9 @@
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(size = 8, derive(Atomic))]
+enum Bad {
+    A,
+    #[ribbit(size = 7)]
+    B(ribbit::u7),
+}
+
+fn main() {}
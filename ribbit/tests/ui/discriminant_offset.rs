@@ -0,0 +1,8 @@
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(size = 8, discriminant(size = 2, offset = 7))]
+enum Foo {
+    A,
+    B,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(size = 4)]
+enum Foo {
+    Bar = 1 << 4,
+    Baz = -9,
+}
+
+fn main() {
+    let _ = ribbit::Packed::<Foo>::new_bar();
+}
@@ -0,0 +1,9 @@
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(size = 8, discriminant(size = 2, offset = 4))]
+enum Foo {
+    #[ribbit(size = 6)]
+    A(ribbit::u6),
+    B,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(size = 8, discriminant = "prefix")]
+enum Foo {
+    #[ribbit(size = 7)]
+    A(ribbit::u7),
+    #[ribbit(size = 7)]
+    B(ribbit::u7),
+    C,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(size = 8, discriminant(size = 1))]
+enum Foo {
+    A,
+    B,
+    C,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(size = 24)]
+struct Padded {
+    count: u16,
+    #[ribbit(guard)]
+    overflow: bool,
+}
+
+fn main() {}
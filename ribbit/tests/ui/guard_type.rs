@@ -0,0 +1,9 @@
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(size = 16)]
+struct Bad {
+    count: u8,
+    #[ribbit(guard, non_zero)]
+    overflow: ribbit::NonZeroU8,
+}
+
+fn main() {}
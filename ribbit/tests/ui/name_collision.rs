@@ -0,0 +1,19 @@
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(size = 9)]
+enum Op {
+    #[ribbit(size = 8)]
+    Discriminant(u8),
+    #[ribbit(size = 8)]
+    IntoRaw(u8),
+}
+
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(size = 9, variants(discriminant(rename = "tag")))]
+enum Renamed {
+    #[ribbit(size = 8)]
+    Discriminant(u8),
+    #[ribbit(size = 8)]
+    IntoRaw(u8),
+}
+
+fn main() {}
@@ -0,0 +1,29 @@
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(size = 4)]
+enum Inner {
+    A,
+    B,
+    C,
+}
+
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(size = 4)]
+enum Outer {
+    #[ribbit(size = 4)]
+    X(crate::Inner),
+    #[ribbit(size = 4, non_exhaustive)]
+    Unknown(ribbit::u4),
+}
+
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(size = 4, discriminant = "prefix")]
+enum Prefix {
+    #[ribbit(size = 3)]
+    A(ribbit::u3),
+    #[ribbit(size = 3)]
+    B(ribbit::u3),
+    #[ribbit(size = 4, non_exhaustive)]
+    Unknown(ribbit::u4),
+}
+
+fn main() {}
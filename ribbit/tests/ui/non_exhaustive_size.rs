@@ -0,0 +1,9 @@
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(size = 8)]
+enum Foo {
+    A,
+    #[ribbit(size = 4, non_exhaustive)]
+    Unknown(ribbit::u4),
+}
+
+fn main() {}
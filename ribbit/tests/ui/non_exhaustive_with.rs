@@ -0,0 +1,13 @@
+use ribbit::Pack as _;
+
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(size = 2, discriminant(size = 1))]
+enum Foo {
+    A,
+    #[ribbit(size = 2, non_exhaustive)]
+    Unknown(ribbit::u2),
+}
+
+fn main() {
+    let _ = Foo::A.pack().with_unknown_0(ribbit::u2::new(0));
+}
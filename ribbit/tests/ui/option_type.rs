@@ -0,0 +1,8 @@
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(size = 9)]
+struct Foo {
+    #[ribbit(option = "flag")]
+    a: u8,
+}
+
+fn main() {}
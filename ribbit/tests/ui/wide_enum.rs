@@ -0,0 +1,8 @@
+#[derive(ribbit::Pack, Copy, Clone)]
+#[ribbit(size = 192)]
+enum Bad {
+    A(u64),
+    B(u128),
+}
+
+fn main() {}
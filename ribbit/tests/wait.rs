@@ -0,0 +1,78 @@
+#![cfg(all(feature = "std", not(all(loom, feature = "loom"))))]
+
+use core::sync::atomic::Ordering;
+use std::thread;
+use std::time::Duration;
+
+use ribbit::u31;
+use ribbit::u63;
+use ribbit::Atomic;
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 32, derive(Debug, Eq))]
+struct Futex {
+    locked: bool,
+    epoch: u31,
+}
+
+#[test]
+fn futex() {
+    let state = Atomic::<Futex>::new(Futex {
+        locked: true,
+        epoch: u31::new(0),
+    });
+
+    thread::scope(|scope| {
+        let waiters = (0..4)
+            .map(|_| scope.spawn(|| state.wait_until(|state| !state.locked)))
+            .collect::<Vec<_>>();
+
+        thread::sleep(Duration::from_millis(10));
+        state.store(
+            Futex {
+                locked: false,
+                epoch: u31::new(1),
+            },
+            Ordering::Release,
+        );
+        state.notify_all();
+
+        for waiter in waiters {
+            assert_eq!(waiter.join().unwrap().epoch, u31::new(1));
+        }
+    });
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 64, derive(Debug, Eq))]
+struct Park {
+    locked: bool,
+    epoch: u63,
+}
+
+#[test]
+fn park() {
+    let state = Atomic::<Park>::new(Park {
+        locked: true,
+        epoch: u63::new(0),
+    });
+
+    thread::scope(|scope| {
+        let waiter = scope.spawn(|| state.wait_until(|state| !state.locked));
+
+        // Waiting on an unexpected value returns immediately
+        state.wait(ribbit::Packed::<Park>::new(false, u63::new(0)));
+
+        thread::sleep(Duration::from_millis(10));
+        state.store(
+            Park {
+                locked: false,
+                epoch: u63::new(1),
+            },
+            Ordering::Release,
+        );
+        state.notify_one();
+
+        assert_eq!(waiter.join().unwrap().epoch, u63::new(1));
+    });
+}
@@ -0,0 +1,219 @@
+use ribbit::i54;
+use ribbit::u4;
+use ribbit::u60;
+use ribbit::Integer as _;
+use ribbit::Invalid;
+use ribbit::Pack as _;
+use ribbit::Unpack as _;
+use ribbit::Words;
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 8, derive(Debug, Eq))]
+enum Kind {
+    A,
+    #[ribbit(size = 4)]
+    B(u4),
+    C,
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 200, derive(Debug, Eq, Ord, Hash))]
+struct Header {
+    a: u60,
+    // Bits 60..68
+    b: u8,
+    // Bits 68..132
+    c: u64,
+    flag: bool,
+    #[ribbit(size = 8)]
+    kind: crate::Kind,
+    #[ribbit(option = "flag")]
+    extra: Option<u4>,
+    // Bits 146..200
+    e: i54,
+}
+
+type Packed = ribbit::Packed<Header>;
+
+fn header() -> Header {
+    Header {
+        a: u60::new(0x0012_3456_789a_bcde),
+        b: 0xa5,
+        c: 0xfedc_ba98_7654_3210,
+        flag: true,
+        kind: Kind::B(u4::new(9)),
+        extra: Some(u4::new(3)),
+        e: i54::new(0xf_dead_beef_cafe),
+    }
+}
+
+fn zero() -> Packed {
+    Packed::try_from_raw(Words::new([0; 4])).unwrap()
+}
+
+#[test]
+fn straddle() {
+    assert_eq!(
+        zero().with_b(0xff).into_raw().value(),
+        [0xf << 60, 0xf, 0, 0]
+    );
+    assert_eq!(
+        zero().with_c(u64::MAX).into_raw().value(),
+        [0, !0 << 4, 0xf, 0]
+    );
+    assert_eq!(
+        zero().with_e(i54::MAX).into_raw().value(),
+        [0, 0, !0 << 18, 0x7f]
+    );
+
+    let packed = header().pack();
+    assert_eq!(packed.b(), 0xa5);
+    assert_eq!(packed.c(), 0xfedc_ba98_7654_3210);
+    assert_eq!(packed.e(), i54::new(0xf_dead_beef_cafe));
+
+    // Sign extension must not clobber neighboring fields
+    let packed = zero().with_e(i54::new(-2));
+    assert_eq!(packed.into_raw().value(), [0, 0, !0 << 19, 0xff]);
+    assert_eq!(packed.e(), i54::new(-2));
+    assert_eq!(packed.with_e(i54::MIN).e(), i54::MIN);
+    assert_eq!(packed.unpack().e, i54::new(-2));
+}
+
+#[test]
+fn round_trip() {
+    let header = header();
+    let packed = header.pack();
+
+    assert_eq!(packed.unpack(), header);
+    assert_eq!(packed.a(), header.a);
+    assert!(packed.flag());
+    assert_eq!(packed.kind().unpack(), Kind::B(u4::new(9)));
+    assert_eq!(packed.extra(), Some(u4::new(3)));
+
+    let packed = packed.with_kind(Kind::C.pack()).with_extra(None);
+    assert_eq!(
+        packed.unpack(),
+        Header {
+            kind: Kind::C,
+            extra: None,
+            ..header
+        }
+    );
+
+    assert_eq!(
+        unsafe { Packed::from_raw_unchecked(packed.into_raw()) },
+        packed
+    );
+}
+
+#[test]
+fn ord() {
+    assert!(zero() < zero().with_flag(true));
+    assert!(zero().with_a(u60::MAX) < zero().with_e(i54::new(1)));
+}
+
+#[test]
+fn try_from_raw() {
+    let raw = header().pack().into_raw().value();
+
+    let mut unused = raw;
+    unused[3] |= 1 << 8;
+    let error = Packed::try_from(Words::new(unused)).unwrap_err();
+    assert_eq!(error.kind(), Invalid::Unused);
+    assert_eq!(error.name(), "Header");
+    assert_eq!(error.offset(), 200);
+
+    // Discriminant of `kind` at bits 133..135
+    let mut discriminant = raw;
+    discriminant[2] |= 0b11 << 5;
+    let error = Packed::try_from_raw(Words::new(discriminant)).unwrap_err();
+    assert_eq!(error.kind(), Invalid::Discriminant);
+    assert_eq!(error.name(), "Kind");
+    assert_eq!(error.offset(), 133);
+
+    // Presence bit of `extra` at bit 145
+    let mut absent = raw;
+    absent[2] &= !(1 << 17);
+    let error = Packed::try_from_raw(Words::new(absent)).unwrap_err();
+    assert_eq!(error.kind(), Invalid::Unused);
+    assert_eq!(error.name(), "extra");
+    assert_eq!(error.offset(), 141);
+}
+
+#[test]
+fn bytes() {
+    let packed = zero().with_b(0xff).with_e(i54::MAX);
+
+    let bytes = packed.to_le_bytes();
+    assert_eq!(bytes.len(), 25);
+    assert_eq!(bytes[7], 0xf0);
+    assert_eq!(bytes[8], 0x0f);
+    assert_eq!(bytes[24], 0x7f);
+    assert_eq!(Packed::try_from_le_bytes(bytes), Ok(packed));
+    assert_eq!(Packed::try_from_be_bytes(packed.to_be_bytes()), Ok(packed));
+
+    // Discriminant of `kind` at bits 133..135
+    let mut bytes = [0; 25];
+    bytes[24 - 16] = 0b11 << 5;
+    assert_eq!(
+        Packed::try_from_be_bytes(bytes).unwrap_err().kind(),
+        Invalid::Discriminant
+    );
+}
+
+#[derive(ribbit::Pack, Copy, Clone, Debug, PartialEq, Eq)]
+#[ribbit(size = 401, derive(Debug, Eq))]
+struct Pair {
+    #[ribbit(size = 200)]
+    low: crate::Header,
+    // Bits 200..400
+    #[ribbit(size = 200)]
+    high: crate::Header,
+    valid: bool,
+}
+
+#[test]
+fn nested() {
+    let pair = Pair {
+        low: header(),
+        high: Header {
+            e: i54::MAX,
+            ..header()
+        },
+        valid: true,
+    };
+
+    let packed = pair.pack();
+    assert_eq!(packed.unpack(), pair);
+    assert_eq!(packed.high().unpack(), pair.high);
+    assert_eq!(packed.with_low(zero()).low(), zero());
+    assert_eq!(packed.with_high(zero()).high(), zero());
+    assert_eq!(packed.with_high(zero()).low(), packed.low());
+    assert!(packed.with_high(zero()).valid());
+
+    // Discriminant of `high.kind` at bits 333..335
+    let mut raw = packed.into_raw().value();
+    raw[5] |= 0b11 << 13;
+    let error = ribbit::Packed::<Pair>::try_from_raw(Words::new(raw)).unwrap_err();
+    assert_eq!(error.kind(), Invalid::Discriminant);
+    assert_eq!(error.name(), "Kind");
+    assert_eq!(error.offset(), 333);
+}
+
+const HEADER: Packed = Packed::new(
+    u60::new(1),
+    2,
+    3,
+    false,
+    ribbit::Packed::<Kind>::new_a(),
+    None,
+    i54::new(4),
+);
+
+const C: u64 = HEADER.c();
+
+#[test]
+fn r#const() {
+    assert_eq!(C, 3);
+    assert_eq!(HEADER.e(), i54::new(4));
+}